@@ -0,0 +1,530 @@
+use anyhow::{anyhow, bail, Context};
+use netlink_packet_core::{NLM_F_ACK, NLM_F_DUMP};
+use netlink_packet_route::link::nlas::{AfSpecBridge, BridgeVlanInfo, Nla};
+use netlink_packet_route::{BRIDGE_FLAGS_MASTER, BRIDGE_FLAGS_SELF, BRIDGE_VLAN_INFO_PVID, BRIDGE_VLAN_INFO_RANGE_BEGIN, BRIDGE_VLAN_INFO_RANGE_END, BRIDGE_VLAN_INFO_UNTAGGED, LinkMessage, RtnlMessage};
+use netlink_packet_utils::nla::{self, DefaultNla, Nla as _, NlaBuffer, NlasIterator, NLA_F_NESTED};
+use netlink_packet_utils::parsers::{parse_u16, parse_u32, parse_u8};
+use netlink_packet_utils::{DecodeError, Emitable, Parseable};
+
+use crate::handle::NetlinkHandle;
+use crate::nl_linux::{BRIDGE_VLANDB_DUMPF_STATS, BRIDGE_VLANDB_DUMP_FLAGS, BRIDGE_VLANDB_ENTRY, BRIDGE_VLANDB_ENTRY_INFO, BRIDGE_VLANDB_ENTRY_RANGE, BRIDGE_VLANDB_ENTRY_STATE, BRIDGE_VLANDB_ENTRY_STATS, BRIDGE_VLANDB_ENTRY_TUNNEL_INFO, BRIDGE_VLANDB_STATS_RX_BYTES, BRIDGE_VLANDB_STATS_RX_PACKETS, BRIDGE_VLANDB_STATS_TX_BYTES, BRIDGE_VLANDB_STATS_TX_PACKETS, BRIDGE_VLANDB_TINFO_ID, IFLA_BRIDGE_VLAN_TUNNEL_FLAGS, IFLA_BRIDGE_VLAN_TUNNEL_ID, IFLA_BRIDGE_VLAN_TUNNEL_INFO, IFLA_BRIDGE_VLAN_TUNNEL_VID, RTM_GETVLAN, RTM_NEWVLAN};
+use crate::nl_type::{BridgePortState, Family, FAMILY_BRIDGE};
+use crate::rtnl::{RtnlExtMessage, RtnlHeader};
+use crate::utils;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum VlanEntryNla {
+    Info(BridgeVlanInfo),
+    Range(u16),
+    State(u8),
+    TunnelInfo(Vec<DefaultNla>),
+    Stats(Vec<DefaultNla>),
+    Other(DefaultNla),
+}
+
+impl nla::Nla for VlanEntryNla {
+    fn value_len(&self) -> usize {
+        match self {
+            VlanEntryNla::Info(_) => 4,
+            VlanEntryNla::Range(_) => 2,
+            VlanEntryNla::State(_) => 1,
+            VlanEntryNla::TunnelInfo(nlas) | VlanEntryNla::Stats(nlas) => nlas.as_slice().buffer_len(),
+            VlanEntryNla::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            VlanEntryNla::Info(_) => BRIDGE_VLANDB_ENTRY_INFO,
+            VlanEntryNla::Range(_) => BRIDGE_VLANDB_ENTRY_RANGE,
+            VlanEntryNla::State(_) => BRIDGE_VLANDB_ENTRY_STATE,
+            VlanEntryNla::TunnelInfo(_) => BRIDGE_VLANDB_ENTRY_TUNNEL_INFO,
+            VlanEntryNla::Stats(_) => BRIDGE_VLANDB_ENTRY_STATS,
+            VlanEntryNla::Other(attr) => attr.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            VlanEntryNla::Info(info) => buffer.copy_from_slice(&<[u8; 4]>::from(info)),
+            VlanEntryNla::Range(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            VlanEntryNla::State(v) => buffer[0] = *v,
+            VlanEntryNla::TunnelInfo(nlas) | VlanEntryNla::Stats(nlas) => nlas.as_slice().emit(buffer),
+            VlanEntryNla::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn is_nested(&self) -> bool {
+        matches!(self, VlanEntryNla::TunnelInfo(_) | VlanEntryNla::Stats(_))
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for VlanEntryNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            BRIDGE_VLANDB_ENTRY_INFO => VlanEntryNla::Info(BridgeVlanInfo::try_from(payload).context("invalid BRIDGE_VLANDB_ENTRY_INFO value")?),
+            BRIDGE_VLANDB_ENTRY_RANGE => VlanEntryNla::Range(parse_u16(payload).context("invalid BRIDGE_VLANDB_ENTRY_RANGE value")?),
+            BRIDGE_VLANDB_ENTRY_STATE => VlanEntryNla::State(parse_u8(payload).context("invalid BRIDGE_VLANDB_ENTRY_STATE value")?),
+            BRIDGE_VLANDB_ENTRY_TUNNEL_INFO => VlanEntryNla::TunnelInfo(utils::parse_nlas(payload)),
+            BRIDGE_VLANDB_ENTRY_STATS => VlanEntryNla::Stats(utils::parse_nlas(payload)),
+            _ => VlanEntryNla::Other(DefaultNla::parse(buf).context("invalid BRIDGE_VLANDB_ENTRY nla")?),
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum VlanNla {
+    Entry(Vec<VlanEntryNla>),
+    Other(DefaultNla),
+}
+
+impl nla::Nla for VlanNla {
+    fn value_len(&self) -> usize {
+        match self {
+            VlanNla::Entry(nlas) => nlas.as_slice().buffer_len(),
+            VlanNla::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            VlanNla::Entry(_) => BRIDGE_VLANDB_ENTRY,
+            VlanNla::Other(attr) => attr.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            VlanNla::Entry(nlas) => nlas.as_slice().emit(buffer),
+            VlanNla::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn is_nested(&self) -> bool {
+        matches!(self, VlanNla::Entry(_))
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for VlanNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        Ok(match buf.kind() {
+            BRIDGE_VLANDB_ENTRY => {
+                let mut nlas = vec![];
+                for nla in NlasIterator::new(buf.value()) {
+                    nlas.push(VlanEntryNla::parse(&nla?)?);
+                }
+                VlanNla::Entry(nlas)
+            }
+            _ => VlanNla::Other(DefaultNla::parse(buf).context("invalid vlan nla")?),
+        })
+    }
+}
+
+// VlanHeader is struct br_vlan_msg: family, 3 reserved bytes and the ifindex
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct VlanHeader {
+    pub family: Family,
+    pub ifindex: u32,
+}
+
+impl RtnlHeader for VlanHeader {
+    const LEN: usize = 8;
+
+    fn emit(&self, buffer: &mut [u8]) {
+        buffer[0] = self.family;
+        buffer[4..8].copy_from_slice(&self.ifindex.to_ne_bytes());
+    }
+
+    fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        if payload.len() < Self::LEN {
+            return Err(DecodeError::from("vlan message too short"));
+        }
+        Ok(VlanHeader {
+            family: payload[0],
+            ifindex: parse_u32(&payload[4..8])?,
+        })
+    }
+}
+
+// VlanMessage is a RTM_NEWVLAN/RTM_DELVLAN/RTM_GETVLAN message
+pub type VlanMessage = RtnlExtMessage<VlanHeader, VlanNla>;
+
+// BridgeVlanInfo is non exhaustive and can't be built with a struct expression
+fn bridge_vlan_info(flags: u16, vid: u16) -> BridgeVlanInfo {
+    let mut info = BridgeVlanInfo::default();
+    info.flags = flags;
+    info.vid = vid;
+    info
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BridgeVlanStats {
+    pub rx_bytes: u64,
+    pub rx_packets: u64,
+    pub tx_bytes: u64,
+    pub tx_packets: u64,
+}
+
+// BridgeVlan is a vlan of a bridge or bridge port, or a range of vlans when `vid_end`
+// is greater than `vid`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BridgeVlan {
+    pub link_index: u32,
+    pub vid: u16,
+    pub vid_end: u16,
+    pub pvid: bool,
+    pub untagged: bool,
+    // vni the vlan is mapped to on a vxlan port, the vnis of a range are consecutive
+    pub tunnel_id: Option<u32>,
+    // stp state and stats are only reported by bridge_vlan_list
+    pub state: Option<BridgePortState>,
+    pub stats: Option<BridgeVlanStats>,
+}
+
+impl BridgeVlan {
+    fn vlan_flags(&self) -> u16 {
+        let mut flags = 0;
+        if self.pvid {
+            flags |= BRIDGE_VLAN_INFO_PVID;
+        }
+        if self.untagged {
+            flags |= BRIDGE_VLAN_INFO_UNTAGGED;
+        }
+        flags
+    }
+
+    fn vlan_info_nlas(&self) -> Vec<AfSpecBridge> {
+        let vlan_info = |flags, vid| AfSpecBridge::VlanInfo(bridge_vlan_info(flags, vid));
+        if self.vid_end > self.vid {
+            vec![
+                vlan_info(self.vlan_flags() | BRIDGE_VLAN_INFO_RANGE_BEGIN, self.vid),
+                vlan_info(self.vlan_flags() | BRIDGE_VLAN_INFO_RANGE_END, self.vid_end),
+            ]
+        } else {
+            vec![vlan_info(self.vlan_flags(), self.vid)]
+        }
+    }
+
+    fn tunnel_info_nlas(&self, tunnel_id: u32) -> Vec<AfSpecBridge> {
+        let tunnel_info = |id: u32, vid: u16, flags: u16| {
+            let nlas = vec![
+                DefaultNla::new(IFLA_BRIDGE_VLAN_TUNNEL_ID, id.to_ne_bytes().to_vec()),
+                DefaultNla::new(IFLA_BRIDGE_VLAN_TUNNEL_VID, vid.to_ne_bytes().to_vec()),
+                DefaultNla::new(IFLA_BRIDGE_VLAN_TUNNEL_FLAGS, flags.to_ne_bytes().to_vec()),
+            ];
+            AfSpecBridge::Other(DefaultNla::new(IFLA_BRIDGE_VLAN_TUNNEL_INFO | NLA_F_NESTED, utils::emit_nlas(&nlas)))
+        };
+        if self.vid_end > self.vid {
+            let tunnel_id_end = tunnel_id + (self.vid_end - self.vid) as u32;
+            vec![
+                tunnel_info(tunnel_id, self.vid, BRIDGE_VLAN_INFO_RANGE_BEGIN),
+                tunnel_info(tunnel_id_end, self.vid_end, BRIDGE_VLAN_INFO_RANGE_END),
+            ]
+        } else {
+            vec![tunnel_info(tunnel_id, self.vid, 0)]
+        }
+    }
+
+    fn from_entry_nlas(link_index: u32, nlas: &[VlanEntryNla]) -> Self {
+        let mut vlan = BridgeVlan {
+            link_index,
+            ..Default::default()
+        };
+        for nla in nlas {
+            match nla {
+                VlanEntryNla::Info(info) => {
+                    vlan.vid = info.vid;
+                    vlan.pvid = info.flags & BRIDGE_VLAN_INFO_PVID != 0;
+                    vlan.untagged = info.flags & BRIDGE_VLAN_INFO_UNTAGGED != 0;
+                }
+                VlanEntryNla::Range(v) => vlan.vid_end = *v,
+                VlanEntryNla::State(v) => vlan.state = BridgePortState::try_from(*v).ok(),
+                VlanEntryNla::TunnelInfo(nlas) => {
+                    vlan.tunnel_id = nlas.iter()
+                        .find(|nla| nla.kind() == BRIDGE_VLANDB_TINFO_ID)
+                        .and_then(utils::default_nla_u32);
+                }
+                VlanEntryNla::Stats(nlas) => {
+                    let mut stats = BridgeVlanStats::default();
+                    for nla in nlas {
+                        let Ok(value) = utils::default_nla_value(nla).try_into().map(u64::from_ne_bytes) else {
+                            continue;
+                        };
+                        match nla.kind() {
+                            BRIDGE_VLANDB_STATS_RX_BYTES => stats.rx_bytes = value,
+                            BRIDGE_VLANDB_STATS_RX_PACKETS => stats.rx_packets = value,
+                            BRIDGE_VLANDB_STATS_TX_BYTES => stats.tx_bytes = value,
+                            BRIDGE_VLANDB_STATS_TX_PACKETS => stats.tx_packets = value,
+                            _ => {}
+                        }
+                    }
+                    vlan.stats = Some(stats);
+                }
+                _ => {}
+            }
+        }
+        vlan.vid_end = vlan.vid_end.max(vlan.vid);
+        vlan
+    }
+}
+
+impl TryFrom<&VlanMessage> for Vec<BridgeVlan> {
+    type Error = anyhow::Error;
+
+    fn try_from(msg: &VlanMessage) -> Result<Self, Self::Error> {
+        if msg.message_type != RTM_NEWVLAN {
+            return Err(anyhow!("msg is not new vlan"));
+        }
+        Ok(msg.nlas.iter()
+            .filter_map(|nla| crate::unwrap_enum!(nla, VlanNla::Entry))
+            .map(|nlas| BridgeVlan::from_entry_nlas(msg.header.ifindex, nlas))
+            .collect())
+    }
+}
+
+// the IFLA_AF_SPEC message of RTM_SETLINK/RTM_DELLINK, `self_` targets the device itself,
+// e.g. the bridge or a switchdev port, and `master` the bridge it is enslaved to.
+// The kernel applies the change to the master when neither is set.
+fn af_spec_msg(link_index: u32, self_: bool, master: bool, mut nlas: Vec<AfSpecBridge>) -> LinkMessage {
+    let mut flags = 0;
+    if self_ {
+        flags |= BRIDGE_FLAGS_SELF;
+    }
+    if master {
+        flags |= BRIDGE_FLAGS_MASTER;
+    }
+    if flags != 0 {
+        nlas.insert(0, AfSpecBridge::Flags(flags));
+    }
+    let mut msg = LinkMessage::default();
+    msg.header.interface_family = FAMILY_BRIDGE;
+    msg.header.index = link_index;
+    msg.nlas.push(Nla::AfSpecBridge(nlas));
+    msg
+}
+
+// bridge_vlan_add adds a vlan, or a range of vlans, to a bridge port or to the bridge
+// itself with `self_`. Only a single vlan can be the pvid.
+// Equivalent to: `bridge vlan add dev $link vid $vid-$vid_end [pvid] [untagged] [self] [master]`
+pub fn bridge_vlan_add(vlan: &BridgeVlan, self_: bool, master: bool) -> anyhow::Result<()> {
+    if vlan.pvid && vlan.vid_end > vlan.vid {
+        bail!("pvid can not be set on vlan range {}-{}", vlan.vid, vlan.vid_end);
+    }
+    let msg = af_spec_msg(vlan.link_index, self_, master, vlan.vlan_info_nlas());
+    NetlinkHandle::new().execute(RtnlMessage::SetLink(msg), NLM_F_ACK)?;
+    Ok(())
+}
+
+// bridge_vlan_del deletes a vlan, or a range of vlans, from a bridge port or from the
+// bridge itself with `self_`.
+// Equivalent to: `bridge vlan del dev $link vid $vid-$vid_end [self] [master]`
+pub fn bridge_vlan_del(vlan: &BridgeVlan, self_: bool, master: bool) -> anyhow::Result<()> {
+    let msg = af_spec_msg(vlan.link_index, self_, master, vlan.vlan_info_nlas());
+    NetlinkHandle::new().execute(RtnlMessage::DelLink(msg), NLM_F_ACK)?;
+    Ok(())
+}
+
+// bridge_vlan_tunnel_add maps the vlans of a vxlan bridge port to the vnis starting at
+// `tunnel_id`. The port needs `vlan_tunnel` enabled and the vlans must already exist.
+// Equivalent to: `bridge vlan add dev $link vid $vid-$vid_end tunnel_info id $tunnel_id-$tunnel_id_end`
+pub fn bridge_vlan_tunnel_add(vlan: &BridgeVlan, self_: bool, master: bool) -> anyhow::Result<()> {
+    let Some(tunnel_id) = vlan.tunnel_id else {
+        bail!("tunnel_id is required to map vlan {} to a tunnel", vlan.vid);
+    };
+    let msg = af_spec_msg(vlan.link_index, self_, master, vlan.tunnel_info_nlas(tunnel_id));
+    NetlinkHandle::new().execute(RtnlMessage::SetLink(msg), NLM_F_ACK)?;
+    Ok(())
+}
+
+// bridge_vlan_tunnel_del removes the vni mapping of the vlans of a vxlan bridge port,
+// the vlans themselves are kept.
+// Equivalent to: `bridge vlan del dev $link vid $vid-$vid_end tunnel_info id $tunnel_id-$tunnel_id_end`
+pub fn bridge_vlan_tunnel_del(vlan: &BridgeVlan, self_: bool, master: bool) -> anyhow::Result<()> {
+    let Some(tunnel_id) = vlan.tunnel_id else {
+        bail!("tunnel_id is required to unmap vlan {} from a tunnel", vlan.vid);
+    };
+    let msg = af_spec_msg(vlan.link_index, self_, master, vlan.tunnel_info_nlas(tunnel_id));
+    NetlinkHandle::new().execute(RtnlMessage::DelLink(msg), NLM_F_ACK)?;
+    Ok(())
+}
+
+// bridge_vlan_list returns the vlans of a bridge or bridge port, or of every bridge and
+// bridge port when `link_index` is 0. Consecutive vlans with the same flags, state and
+// tunnel mapping are merged into ranges unless `stats` is requested, in which case every
+// vlan is reported on its own with its counters.
+// Equivalent to: `bridge [-s] vlan show dev $link`
+pub fn bridge_vlan_list(link_index: u32, stats: bool) -> anyhow::Result<Vec<BridgeVlan>> {
+    let mut msg = VlanMessage {
+        message_type: RTM_GETVLAN,
+        header: VlanHeader {
+            family: FAMILY_BRIDGE,
+            ifindex: link_index,
+        },
+        nlas: vec![],
+    };
+    if stats {
+        let flags = BRIDGE_VLANDB_DUMPF_STATS.to_ne_bytes().to_vec();
+        msg.nlas.push(VlanNla::Other(DefaultNla::new(BRIDGE_VLANDB_DUMP_FLAGS, flags)));
+    }
+    let resp = NetlinkHandle::new().execute(msg, NLM_F_DUMP | NLM_F_ACK)?;
+    let mut vlans = vec![];
+    for msg in &resp {
+        vlans.extend(Vec::<BridgeVlan>::try_from(msg)?);
+    }
+    Ok(vlans)
+}
+
+#[cfg(test)]
+mod tests {
+    use netlink_packet_core::{NetlinkDeserializable, NetlinkHeader, NetlinkSerializable};
+
+    use crate::nl_type::{Bridge, BridgeSlave, Tuntap, Vxlan, TUNTAP_MODE_TAP};
+    use crate::{link_add, link_by_name, link_del, link_set_bridge_slave, link_set_master, tuntap_add, Link, LinkAttrs, LinkKind};
+
+    use super::*;
+
+    fn vlan_bridge(name: &str) -> anyhow::Result<Link> {
+        let bridge = Link {
+            link_attrs: LinkAttrs {
+                name: name.to_string(),
+                ..Default::default()
+            },
+            link_kind: LinkKind::Bridge(Bridge {
+                vlan_filtering: Some(true),
+                ..Default::default()
+            }),
+        };
+        link_add(&bridge)?;
+        Ok(link_by_name(name)?.unwrap())
+    }
+
+    #[test]
+    fn test_vlan_parse() -> anyhow::Result<()> {
+        let info = bridge_vlan_info(BRIDGE_VLAN_INFO_PVID | BRIDGE_VLAN_INFO_UNTAGGED, 10);
+        let stats = vec![
+            DefaultNla::new(BRIDGE_VLANDB_STATS_RX_BYTES, 100u64.to_ne_bytes().to_vec()),
+            DefaultNla::new(BRIDGE_VLANDB_STATS_TX_PACKETS, 2u64.to_ne_bytes().to_vec()),
+        ];
+        let tunnel = vec![DefaultNla::new(BRIDGE_VLANDB_TINFO_ID, 1000u32.to_ne_bytes().to_vec())];
+        let msg = VlanMessage {
+            message_type: RTM_NEWVLAN,
+            header: VlanHeader {
+                family: FAMILY_BRIDGE,
+                ifindex: 7,
+            },
+            nlas: vec![
+                VlanNla::Entry(vec![VlanEntryNla::Info(info), VlanEntryNla::State(3), VlanEntryNla::Stats(stats)]),
+                VlanNla::Entry(vec![VlanEntryNla::Info(bridge_vlan_info(0, 20)), VlanEntryNla::Range(30), VlanEntryNla::TunnelInfo(tunnel)]),
+            ],
+        };
+        let mut buf = vec![0u8; msg.buffer_len()];
+        msg.serialize(&mut buf);
+        let mut header = NetlinkHeader::default();
+        header.message_type = RTM_NEWVLAN;
+        let parsed = VlanMessage::deserialize(&header, &buf)?;
+        assert_eq!(parsed, msg);
+        let vlans = Vec::<BridgeVlan>::try_from(&parsed)?;
+        assert_eq!(vlans, vec![
+            BridgeVlan {
+                link_index: 7,
+                vid: 10,
+                vid_end: 10,
+                pvid: true,
+                untagged: true,
+                state: Some(BridgePortState::Forwarding),
+                stats: Some(BridgeVlanStats { rx_bytes: 100, tx_packets: 2, ..Default::default() }),
+                ..Default::default()
+            },
+            BridgeVlan {
+                link_index: 7,
+                vid: 20,
+                vid_end: 30,
+                tunnel_id: Some(1000),
+                ..Default::default()
+            },
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_bridge_vlan_add_del_list() -> anyhow::Result<()> {
+        let bridge = vlan_bridge("br237")?;
+        let mut port = Link {
+            link_attrs: LinkAttrs {
+                name: "tap237".to_string(),
+                ..Default::default()
+            },
+            link_kind: LinkKind::Tuntap(Tuntap {
+                mode: TUNTAP_MODE_TAP,
+                ..Default::default()
+            }),
+        };
+        tuntap_add(&mut port)?;
+        let link_index = port.link_attrs.index;
+
+        let result = (|| {
+            link_set_master(&port, &bridge)?;
+            bridge_vlan_add(&BridgeVlan { link_index, vid: 10, vid_end: 10, pvid: true, untagged: true, ..Default::default() }, false, true)?;
+            bridge_vlan_add(&BridgeVlan { link_index, vid: 20, vid_end: 30, ..Default::default() }, false, false)?;
+            bridge_vlan_del(&BridgeVlan { link_index, vid: 26, vid_end: 30, ..Default::default() }, false, false)?;
+            // the default pvid 1 of the port is dropped as 10 became the pvid
+            bridge_vlan_del(&BridgeVlan { link_index, vid: 1, vid_end: 1, ..Default::default() }, false, false)?;
+            Ok::<_, anyhow::Error>((bridge_vlan_list(link_index, false)?, bridge_vlan_list(link_index, true)?))
+        })();
+        link_del(link_index)?;
+        link_del(bridge.as_index())?;
+
+        let (vlans, vlans_with_stats) = result?;
+        let vlans: Vec<_> = vlans.into_iter().map(|vlan| (vlan.vid, vlan.vid_end, vlan.pvid, vlan.untagged)).collect();
+        assert_eq!(vlans, vec![(10, 10, true, true), (20, 25, false, false)]);
+        assert_eq!(vlans_with_stats.len(), 7);
+        assert!(vlans_with_stats.iter().all(|vlan| vlan.stats.is_some() && vlan.state.is_some()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_bridge_vlan_add_pvid_range() {
+        let vlan = BridgeVlan { link_index: 1, vid: 10, vid_end: 20, pvid: true, ..Default::default() };
+        let err = bridge_vlan_add(&vlan, false, false).unwrap_err();
+        assert_eq!(err.to_string(), "pvid can not be set on vlan range 10-20");
+    }
+
+    #[test]
+    fn test_bridge_vlan_tunnel() -> anyhow::Result<()> {
+        let bridge = vlan_bridge("br238")?;
+        let vxlan = Link {
+            link_attrs: LinkAttrs {
+                name: "vxlan238".to_string(),
+                ..Default::default()
+            },
+            link_kind: LinkKind::Vxlan(Vxlan {
+                flow_based: true,
+                port: 4792,
+                ..Default::default()
+            }),
+        };
+        link_add(&vxlan)?;
+        let vxlan = link_by_name("vxlan238")?.unwrap();
+        let link_index = vxlan.as_index();
+
+        let result = (|| {
+            link_set_master(&vxlan, &bridge)?;
+            link_set_bridge_slave(&vxlan, &BridgeSlave { vlan_tunnel: Some(true), ..Default::default() })?;
+            let vlan = BridgeVlan { link_index, vid: 100, vid_end: 102, tunnel_id: Some(10100), ..Default::default() };
+            bridge_vlan_add(&vlan, false, false)?;
+            bridge_vlan_tunnel_add(&vlan, false, false)?;
+            bridge_vlan_tunnel_del(&BridgeVlan { vid: 102, vid_end: 102, tunnel_id: Some(10102), ..vlan.clone() }, false, false)?;
+            bridge_vlan_list(link_index, false)
+        })();
+        link_del(link_index)?;
+        link_del(bridge.as_index())?;
+
+        let tunnels: Vec<_> = result?.into_iter()
+            .filter(|vlan| vlan.vid >= 100)
+            .map(|vlan| (vlan.vid, vlan.vid_end, vlan.tunnel_id))
+            .collect();
+        assert_eq!(tunnels, vec![(100, 101, Some(10100)), (102, 102, None)]);
+        Ok(())
+    }
+}
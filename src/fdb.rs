@@ -0,0 +1,235 @@
+use std::net::IpAddr;
+
+use macaddr::MacAddr6;
+use netlink_packet_core::{NLM_F_ACK, NLM_F_APPEND, NLM_F_CREATE, NLM_F_DUMP, NLM_F_EXCL, NLM_F_REPLACE};
+use netlink_packet_route::{NeighbourMessage, RtnlMessage};
+use netlink_packet_route::neighbour::Nla;
+
+use crate::{LinkIndex, NeighFlags, NudState, utils};
+use crate::handle::NetlinkHandle;
+use crate::nl_type::FAMILY_BRIDGE;
+
+// Fdb is a forwarding database entry of a bridge or vxlan device
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fdb {
+    // the bridge port or vxlan device the entry belongs to
+    pub link_index: u32,
+    pub hardware_addr: MacAddr6,
+    // NOARP (static), PERMANENT or REACHABLE (dynamic), NOARP if empty
+    pub state: NudState,
+    // SELF, MASTER, ROUTER, EXT_LEARNED..., SELF if neither self nor master is set
+    pub flags: NeighFlags,
+    pub master_index: u32,
+    pub vlan: u16,
+    // remote vtep of a vxlan entry
+    pub dst: Option<IpAddr>,
+    pub port: u16,
+    pub vni: u32,
+    pub src_vni: u32,
+    // outgoing interface to reach the remote vtep
+    pub ifindex: u32,
+}
+
+impl Default for Fdb {
+    fn default() -> Self {
+        Self {
+            link_index: 0,
+            hardware_addr: MacAddr6::nil(),
+            state: NudState::empty(),
+            flags: NeighFlags::empty(),
+            master_index: 0,
+            vlan: 0,
+            dst: None,
+            port: 0,
+            vni: 0,
+            src_vni: 0,
+            ifindex: 0,
+        }
+    }
+}
+
+// fdb_add adds a fdb entry, fails if it already exists.
+// Equivalent to: `bridge fdb add $mac dev $link`
+pub fn fdb_add(fdb: &Fdb) -> anyhow::Result<()> {
+    fdb_modify(fdb, NLM_F_CREATE | NLM_F_EXCL | NLM_F_ACK)
+}
+
+// fdb_append appends a fdb entry, used to add several remote vteps for the same mac.
+// Equivalent to: `bridge fdb append $mac dev $link dst $dst`
+pub fn fdb_append(fdb: &Fdb) -> anyhow::Result<()> {
+    fdb_modify(fdb, NLM_F_CREATE | NLM_F_APPEND | NLM_F_ACK)
+}
+
+// fdb_replace adds or replaces a fdb entry.
+// Equivalent to: `bridge fdb replace $mac dev $link`
+pub fn fdb_replace(fdb: &Fdb) -> anyhow::Result<()> {
+    fdb_modify(fdb, NLM_F_CREATE | NLM_F_REPLACE | NLM_F_ACK)
+}
+
+fn fdb_modify(fdb: &Fdb, flags: u16) -> anyhow::Result<()> {
+    let msg = fdb_msg(fdb);
+    let _ = NetlinkHandle::new().execute(RtnlMessage::NewNeighbour(msg), flags)?;
+    Ok(())
+}
+
+// fdb_del deletes a fdb entry.
+// Equivalent to: `bridge fdb del $mac dev $link`
+pub fn fdb_del(fdb: &Fdb) -> anyhow::Result<()> {
+    let msg = fdb_msg(fdb);
+    let _ = NetlinkHandle::new().execute(RtnlMessage::DelNeighbour(msg), NLM_F_ACK)?;
+    Ok(())
+}
+
+// fdb_list lists the fdb entries of a bridge and/or of a port, 0 matches any.
+// Equivalent to: `bridge fdb show br $bridge brport $port`
+pub fn fdb_list(bridge_index: LinkIndex, port_index: LinkIndex) -> anyhow::Result<Vec<Fdb>> {
+    let mut msg = NeighbourMessage::default();
+    msg.header.family = FAMILY_BRIDGE;
+
+    let resp = NetlinkHandle::new().execute(RtnlMessage::GetNeighbour(msg), NLM_F_DUMP | NLM_F_ACK)?;
+    let mut result = Vec::new();
+    for msg in &resp {
+        if let RtnlMessage::NewNeighbour(msg) = msg {
+            if msg.header.family != FAMILY_BRIDGE {
+                continue;
+            }
+            let fdb = Fdb::try_from(msg)?;
+            if bridge_index != 0 && fdb.master_index != bridge_index && fdb.link_index != bridge_index {
+                continue;
+            }
+            if port_index != 0 && fdb.link_index != port_index {
+                continue;
+            }
+            result.push(fdb);
+        }
+    }
+    Ok(result)
+}
+
+fn fdb_msg(fdb: &Fdb) -> NeighbourMessage {
+    let mut msg = NeighbourMessage::default();
+    msg.header.family = FAMILY_BRIDGE;
+    msg.header.ifindex = fdb.link_index;
+    let state = if fdb.state.is_empty() { NudState::NOARP } else { fdb.state };
+    let mut flags = fdb.flags;
+    if !flags.intersects(NeighFlags::SELF | NeighFlags::MASTER) {
+        flags |= NeighFlags::SELF;
+    }
+    msg.header.state = state.bits();
+    msg.header.flags = flags.bits();
+
+    msg.nlas.push(Nla::LinkLocalAddress(fdb.hardware_addr.into_array().to_vec()));
+    if let Some(dst) = &fdb.dst {
+        msg.nlas.push(Nla::Destination(utils::ip_to_bytes(dst)));
+    }
+    if fdb.vlan > 0 {
+        msg.nlas.push(Nla::Vlan(fdb.vlan));
+    }
+    if fdb.port > 0 {
+        msg.nlas.push(Nla::Port(fdb.port.to_be_bytes().to_vec()));
+    }
+    if fdb.vni > 0 {
+        msg.nlas.push(Nla::Vni(fdb.vni));
+    }
+    if fdb.src_vni > 0 {
+        msg.nlas.push(Nla::SourceVni(fdb.src_vni));
+    }
+    if fdb.ifindex > 0 {
+        msg.nlas.push(Nla::IfIndex(fdb.ifindex));
+    }
+    if fdb.master_index > 0 {
+        msg.nlas.push(Nla::Master(fdb.master_index.to_ne_bytes().to_vec()));
+    }
+    msg
+}
+
+impl TryFrom<&NeighbourMessage> for Fdb {
+    type Error = anyhow::Error;
+
+    fn try_from(msg: &NeighbourMessage) -> Result<Self, Self::Error> {
+        let mut fdb = Fdb {
+            link_index: msg.header.ifindex,
+            state: NudState::from_bits_retain(msg.header.state),
+            flags: NeighFlags::from_bits_retain(msg.header.flags),
+            ..Default::default()
+        };
+        for nla in &msg.nlas {
+            match nla {
+                Nla::LinkLocalAddress(bytes) if bytes.len() == 6 => {
+                    fdb.hardware_addr = MacAddr6::new(bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5]);
+                }
+                Nla::Destination(bytes) => {
+                    fdb.dst = Some(utils::bytes_to_ip_by_len(bytes)?);
+                }
+                Nla::Vlan(vlan) => {
+                    fdb.vlan = *vlan;
+                }
+                Nla::Port(bytes) if bytes.len() == 2 => {
+                    fdb.port = u16::from_be_bytes([bytes[0], bytes[1]]);
+                }
+                Nla::Vni(vni) => {
+                    fdb.vni = *vni;
+                }
+                Nla::SourceVni(vni) => {
+                    fdb.src_vni = *vni;
+                }
+                Nla::IfIndex(ifindex) => {
+                    fdb.ifindex = *ifindex;
+                }
+                Nla::Master(bytes) if bytes.len() == 4 => {
+                    fdb.master_index = u32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+                }
+                _ => {}
+            }
+        }
+        Ok(fdb)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::TryAsLinkIndex;
+
+    use super::*;
+
+    #[test]
+    fn test_fdb_append_vxlan() -> anyhow::Result<()> {
+        let vxlan_id = "vxlan0".try_as_index()?.unwrap();
+        for dst in ["10.0.0.2", "10.0.0.3"] {
+            fdb_append(&Fdb {
+                link_index: vxlan_id,
+                state: NudState::PERMANENT,
+                dst: Some(dst.parse()?),
+                ..Default::default()
+            })?;
+        }
+        let list = fdb_list(0, vxlan_id)?;
+        let dsts = list.iter()
+            .filter(|it| it.hardware_addr.is_nil())
+            .filter_map(|it| it.dst)
+            .collect::<Vec<_>>();
+        assert!(dsts.contains(&"10.0.0.2".parse()?));
+        assert!(dsts.contains(&"10.0.0.3".parse()?));
+        Ok(())
+    }
+
+    #[test]
+    fn test_fdb_add_del_master() -> anyhow::Result<()> {
+        let br_id = "br234".try_as_index()?.unwrap();
+        let port_id = "veth234".try_as_index()?.unwrap();
+        let fdb = Fdb {
+            link_index: port_id,
+            hardware_addr: "3a:91:c1:3f:ee:54".parse().unwrap(),
+            flags: NeighFlags::MASTER,
+            ..Default::default()
+        };
+        fdb_add(&fdb)?;
+        let list = fdb_list(br_id, 0)?;
+        assert!(list.iter().any(|it| it.hardware_addr == fdb.hardware_addr && it.link_index == port_id));
+
+        fdb_del(&fdb)?;
+        let list = fdb_list(br_id, port_id)?;
+        assert!(list.iter().all(|it| it.hardware_addr != fdb.hardware_addr));
+        Ok(())
+    }
+}
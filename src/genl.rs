@@ -0,0 +1,109 @@
+use std::fmt::Debug;
+
+use anyhow::{anyhow, Context};
+use netlink_packet_core::{NetlinkDeserializable, NetlinkHeader, NetlinkPayload, NetlinkSerializable, NLM_F_ACK};
+use netlink_packet_utils::nla::{self, DefaultNla, NlaBuffer, NlasIterator};
+use netlink_packet_utils::{DecodeError, Emitable, Parseable};
+use netlink_sys::protocols::NETLINK_GENERIC;
+
+use crate::handle::NetlinkHandle;
+use crate::utils;
+
+pub const GENL_ID_CTRL: u16 = 0x10;
+
+pub const CTRL_CMD_GETFAMILY: u8 = 3;
+
+pub const CTRL_ATTR_FAMILY_ID: u16 = 1;
+pub const CTRL_ATTR_FAMILY_NAME: u16 = 2;
+
+// struct genlmsghdr: cmd, version and 2 reserved bytes
+const GENL_HEADER_LEN: usize = 4;
+
+// GenlMessage is a generic netlink message, the netlink message type is the id of its family
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GenlMessage<T> {
+    pub family_id: u16,
+    pub cmd: u8,
+    pub version: u8,
+    pub nlas: Vec<T>,
+}
+
+impl<T: nla::Nla> NetlinkSerializable for GenlMessage<T> {
+    fn message_type(&self) -> u16 {
+        self.family_id
+    }
+
+    fn buffer_len(&self) -> usize {
+        GENL_HEADER_LEN + self.nlas.as_slice().buffer_len()
+    }
+
+    fn serialize(&self, buffer: &mut [u8]) {
+        buffer[..GENL_HEADER_LEN].fill(0);
+        buffer[0] = self.cmd;
+        buffer[1] = self.version;
+        self.nlas.as_slice().emit(&mut buffer[GENL_HEADER_LEN..]);
+    }
+}
+
+impl<T> NetlinkDeserializable for GenlMessage<T>
+    where T: for<'a> Parseable<NlaBuffer<&'a [u8]>> {
+    type Error = DecodeError;
+
+    fn deserialize(header: &NetlinkHeader, payload: &[u8]) -> Result<Self, Self::Error> {
+        if payload.len() < GENL_HEADER_LEN {
+            return Err(DecodeError::from("generic netlink message too short"));
+        }
+        let mut msg = GenlMessage {
+            family_id: header.message_type,
+            cmd: payload[0],
+            version: payload[1],
+            nlas: vec![],
+        };
+        for nla in NlasIterator::new(&payload[GENL_HEADER_LEN..]) {
+            msg.nlas.push(T::parse(&nla?)?);
+        }
+        Ok(msg)
+    }
+}
+
+impl<T> From<GenlMessage<T>> for NetlinkPayload<GenlMessage<T>> {
+    fn from(msg: GenlMessage<T>) -> Self {
+        NetlinkPayload::InnerMessage(msg)
+    }
+}
+
+// genl_execute sends a request to a generic netlink family and returns the replies
+pub fn genl_execute<T>(msg: GenlMessage<T>, flags: u16) -> anyhow::Result<Vec<GenlMessage<T>>>
+    where T: nla::Nla + for<'a> Parseable<NlaBuffer<&'a [u8]>> + Debug {
+    NetlinkHandle::with_protocol(NETLINK_GENERIC).execute(msg, flags)
+}
+
+// genl_family_id resolves the id of a generic netlink family by name, e.g. "wireguard".
+// Fails with io::ErrorKind::NotFound when the family is not registered.
+pub fn genl_family_id(name: &str) -> anyhow::Result<u16> {
+    let mut family_name = name.as_bytes().to_vec();
+    family_name.push(0);
+    let msg = GenlMessage {
+        family_id: GENL_ID_CTRL,
+        cmd: CTRL_CMD_GETFAMILY,
+        version: 1,
+        nlas: vec![DefaultNla::new(CTRL_ATTR_FAMILY_NAME, family_name)],
+    };
+    let resp = genl_execute(msg, NLM_F_ACK).with_context(|| format!("generic netlink family {}", name))?;
+    resp.iter()
+        .flat_map(|msg| msg.nlas.iter())
+        .find(|nla| nla::Nla::kind(*nla) == CTRL_ATTR_FAMILY_ID)
+        .and_then(utils::default_nla_u16)
+        .ok_or_else(|| anyhow!("generic netlink family {} has no id", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_genl_family_id() -> anyhow::Result<()> {
+        assert_eq!(genl_family_id("nlctrl")?, GENL_ID_CTRL);
+        Ok(())
+    }
+}
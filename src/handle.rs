@@ -1,10 +1,10 @@
+use std::fmt::Debug;
 use std::io::ErrorKind;
 
 use anyhow::{anyhow, bail};
 use bytes::BytesMut;
 use log::{debug, error, info};
-use netlink_packet_core::{NetlinkBuffer, NetlinkDeserializable, NetlinkMessage, NetlinkPayload, NLM_F_ACK, NLM_F_MULTIPART, NLM_F_REQUEST};
-use netlink_packet_route::RtnlMessage;
+use netlink_packet_core::{NetlinkBuffer, NetlinkDeserializable, NetlinkMessage, NetlinkPayload, NetlinkSerializable, NLM_F_ACK, NLM_F_MULTIPART, NLM_F_REQUEST};
 use netlink_sys::protocols::NETLINK_ROUTE;
 use netlink_sys::SocketAddr;
 
@@ -15,7 +15,12 @@ pub struct NetlinkHandle {
 
 impl NetlinkHandle {
     pub fn new() -> NetlinkHandle {
-        let mut socket = netlink_sys::Socket::new(NETLINK_ROUTE).unwrap();
+        Self::with_protocol(NETLINK_ROUTE)
+    }
+
+    // with_protocol opens a handle on another netlink protocol, e.g. NETLINK_GENERIC
+    pub fn with_protocol(protocol: isize) -> NetlinkHandle {
+        let mut socket = netlink_sys::Socket::new(protocol).unwrap();
         socket.bind(&SocketAddr::new(0, 0)).unwrap();
         Self { seq: 0, socket }
     }
@@ -24,19 +29,25 @@ impl NetlinkHandle {
 const INITIAL_READER_CAPACITY: usize = 64 * 1024;
 
 impl NetlinkHandle {
-    pub fn execute(&mut self, msg: RtnlMessage, flags: u16) -> anyhow::Result<Vec<RtnlMessage>> {
+    pub fn execute<T>(&mut self, msg: T, flags: u16) -> anyhow::Result<Vec<T>>
+        where
+            T: NetlinkSerializable + NetlinkDeserializable + Into<NetlinkPayload<T>> + Debug,
+    {
         self.send(msg, flags)?;
         self.recv()
     }
 
-    fn send(&mut self, msg: RtnlMessage, flags: u16) -> anyhow::Result<()> {
+    fn send<T>(&mut self, msg: T, flags: u16) -> anyhow::Result<()>
+        where
+            T: NetlinkSerializable + Into<NetlinkPayload<T>>,
+    {
         let mut packet = NetlinkMessage::from(msg);
         self.seq += 1;
         packet.header.sequence_number = self.seq;
         packet.header.flags = flags | NLM_F_REQUEST | NLM_F_ACK;
         packet.finalize();
 
-        let mut bytes = vec![0u8; 8192];
+        let mut bytes = vec![0u8; packet.buffer_len()];
         packet.serialize(&mut bytes);
         self.socket
             .send(&bytes, 0)
@@ -118,7 +129,10 @@ impl NetlinkHandle {
                 .map_err(|_| anyhow!("IO Error"))?;
         }
     }
-    fn recv(&mut self) -> anyhow::Result<Vec<RtnlMessage>> {
+    fn recv<T>(&mut self) -> anyhow::Result<Vec<T>>
+        where
+            T: NetlinkDeserializable + Debug,
+    {
         let mut result = Vec::new();
         let mut src = BytesMut::with_capacity(INITIAL_READER_CAPACITY);
 
@@ -146,7 +160,8 @@ impl NetlinkHandle {
                             info!(">>>>>>>>>>> code: {:?}", code);
                             Err(std::io::Error::from(ErrorKind::AlreadyExists))?
                         }
-                        bail!("netlink: error response {}", e);
+                        // keep the errno reachable with downcast_ref::<io::Error>()
+                        return Err(anyhow::Error::new(e.to_io()).context(format!("netlink: error response {}", e)));
                     }
                     // info!("recv error empty....");
                     return Ok(result);
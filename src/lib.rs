@@ -12,6 +12,16 @@ mod utils;
 mod rtnl_msg_ext;
 mod neigh;
 pub use neigh::*;
+mod fdb;
+pub use fdb::*;
+mod tuntap;
+pub use tuntap::*;
+pub mod rtnl;
+pub mod netconf;
+pub mod tunnel;
+pub mod bridge;
+pub mod genl;
+pub mod wireguard;
 
 pub use libc::*;
 pub use route::types::*;
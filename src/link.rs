@@ -1,17 +1,21 @@
+use std::collections::BTreeMap;
+use std::net::{IpAddr, Ipv6Addr};
 use std::os::fd::RawFd;
 
 use anyhow::bail;
 use log::{debug, info};
 use macaddr::MacAddr6;
-use netlink_packet_core::{NLM_F_ACK, NLM_F_CREATE, NLM_F_DUMP, NLM_F_EXCL};
-use netlink_packet_route::{AF_UNSPEC, IFF_ALLMULTI, IFF_BROADCAST, IFF_LOOPBACK, IFF_MULTICAST, IFF_POINTOPOINT, IFF_PROMISC, IFF_UP, LinkMessage, RTEXT_FILTER_VF, RtnlMessage};
-use netlink_packet_route::link::nlas::{Info, InfoBridge, InfoData, InfoKind, InfoVxlan, Nla, State, Stats64Buffer, VethInfo};
+use netlink_packet_core::{NetlinkDeserializable, NetlinkHeader, NetlinkPayload, NetlinkSerializable, NLM_F_ACK, NLM_F_CREATE, NLM_F_DUMP, NLM_F_EXCL};
+use netlink_packet_route::{AF_UNSPEC, IFLA_INFO_DATA, IFLA_INFO_KIND, IFLA_LINKINFO, LinkHeader, LinkMessageBuffer, RTM_NEWLINK, IFF_ALLMULTI, IFF_BROADCAST, IFF_LOOPBACK, IFF_MULTICAST, IFF_POINTOPOINT, IFF_PROMISC, IFF_UP, IFLA_PROTINFO, IFLA_BOND_PORT_AD_ACTOR_OPER_PORT_STATE, IFLA_BOND_PORT_AD_AGGREGATOR_ID, IFLA_BOND_PORT_AD_PARTNER_OPER_PORT_STATE, LinkMessage, RTEXT_FILTER_VF, RtnlMessage};
+use netlink_packet_route::link::nlas::{AfSpecInet, BondAdInfo as InfoBondAdInfo, Inet, Inet6, Inet6DevConfBuffer, InetDevConfBuffer, Info, InfoBond, InfoBondPort, InfoBridge, InfoData, InfoIpVlan, InfoKind, InfoMacVlan, InfoPortData, InfoPortKind, InfoVlan, InfoVrf, Nla, State, Stats64Buffer, VethInfo, VlanQosMapping};
 use netlink_packet_route::nlas::link::Stats64;
-use netlink_packet_utils::Parseable;
+use netlink_packet_utils::nla::{DefaultNla, Nla as _, NlaBuffer, NLA_F_NESTED};
+use netlink_packet_utils::{DecodeError, Emitable, Parseable, ParseableParametrized};
 
-use crate::{nl_linux, rtnl_msg_ext, utils};
+use crate::{nl_linux, rtnl_msg_ext, tuntap, utils};
 use crate::handle::NetlinkHandle;
-use crate::nl_type::{Bridge, Dummy, Tuntap, Veth, Vxlan};
+use crate::nl_linux::{GRE_KEY, IFLA_BRPORT_BCAST_FLOOD, IFLA_BRPORT_COST, IFLA_BRPORT_FAST_LEAVE, IFLA_BRPORT_GUARD, IFLA_BRPORT_ISOLATED, IFLA_BRPORT_LEARNING, IFLA_BRPORT_LOCKED, IFLA_BRPORT_MAB, IFLA_BRPORT_MCAST_FLOOD, IFLA_BRPORT_MODE, IFLA_BRPORT_NEIGH_SUPPRESS, IFLA_BRPORT_PRIORITY, IFLA_BRPORT_PROTECT, IFLA_BRPORT_PROXYARP, IFLA_BRPORT_PROXYARP_WIFI, IFLA_BRPORT_STATE, IFLA_BRPORT_UNICAST_FLOOD, IFLA_BRPORT_VLAN_TUNNEL, IFLA_GENEVE_COLLECT_METADATA, IFLA_GENEVE_DF, IFLA_GENEVE_ID, IFLA_GENEVE_INNER_PROTO_INHERIT, IFLA_GENEVE_LABEL, IFLA_GENEVE_PORT, IFLA_GENEVE_REMOTE, IFLA_GENEVE_REMOTE6, IFLA_GENEVE_TOS, IFLA_GENEVE_TTL, IFLA_GENEVE_TTL_INHERIT, IFLA_GENEVE_UDP_CSUM, IFLA_GENEVE_UDP_ZERO_CSUM6_RX, IFLA_GENEVE_UDP_ZERO_CSUM6_TX, IFLA_GRE_COLLECT_METADATA, IFLA_GRE_ENCAP_DPORT, IFLA_GRE_ENCAP_LIMIT, IFLA_GRE_ENCAP_TYPE, IFLA_GRE_ERSPAN_DIR, IFLA_GRE_ERSPAN_HWID, IFLA_GRE_ERSPAN_INDEX, IFLA_GRE_ERSPAN_VER, IFLA_GRE_FLAGS, IFLA_GRE_FLOWINFO, IFLA_GRE_FWMARK, IFLA_GRE_IFLAGS, IFLA_GRE_IGNORE_DF, IFLA_GRE_IKEY, IFLA_GRE_LINK, IFLA_GRE_LOCAL, IFLA_GRE_OFLAGS, IFLA_GRE_OKEY, IFLA_GRE_PMTUDISC, IFLA_GRE_REMOTE, IFLA_GRE_TOS, IFLA_GRE_TTL, IFLA_IPTUN_COLLECT_METADATA, IFLA_IPTUN_ENCAP_DPORT, IFLA_IPTUN_ENCAP_LIMIT, IFLA_IPTUN_ENCAP_TYPE, IFLA_IPTUN_FLAGS, IFLA_IPTUN_FLOWINFO, IFLA_IPTUN_FWMARK, IFLA_IPTUN_LINK, IFLA_IPTUN_LOCAL, IFLA_IPTUN_PMTUDISC, IFLA_IPTUN_PROTO, IFLA_IPTUN_REMOTE, IFLA_IPTUN_TOS, IFLA_IPTUN_TTL, IFLA_VXLAN_AGEING, IFLA_VXLAN_COLLECT_METADATA, IFLA_VXLAN_DF, IFLA_VXLAN_GBP, IFLA_VXLAN_GPE, IFLA_VXLAN_GROUP, IFLA_VXLAN_GROUP6, IFLA_VXLAN_ID, IFLA_VXLAN_L2MISS, IFLA_VXLAN_L3MISS, IFLA_VXLAN_LABEL, IFLA_VXLAN_LEARNING, IFLA_VXLAN_LIMIT, IFLA_VXLAN_LINK, IFLA_VXLAN_LOCAL, IFLA_VXLAN_LOCAL6, IFLA_VXLAN_PORT, IFLA_VXLAN_PORT_RANGE, IFLA_VXLAN_PROXY, IFLA_VXLAN_REMCSUM_NOPARTIAL, IFLA_VXLAN_REMCSUM_RX, IFLA_VXLAN_REMCSUM_TX, IFLA_VXLAN_RSC, IFLA_VXLAN_TOS, IFLA_VXLAN_TTL, IFLA_VXLAN_TTL_INHERIT, IFLA_VXLAN_UDP_CSUM, IFLA_VXLAN_UDP_ZERO_CSUM6_RX, IFLA_VXLAN_UDP_ZERO_CSUM6_TX, IFLA_VXLAN_VNIFILTER, IPV4_DEVCONF_FORWARDING, IPV4_DEVCONF_RP_FILTER, MACVLAN_FLAG_NOPROMISC, MACVLAN_MACADDR_ADD, MACVLAN_MACADDR_DEL, MACVLAN_MACADDR_FLUSH, MACVLAN_MACADDR_SET, VLAN_FLAG_BRIDGE_BINDING, VLAN_FLAG_GVRP, VLAN_FLAG_LOOSE_BINDING, VLAN_FLAG_MVRP, VLAN_FLAG_REORDER_HDR};
+use crate::nl_type::{AddrGenMode, Bond, BondAdInfo, BondAdSelect, BondLacpRate, BondMode, BondSlave, BondXmitHashPolicy, Bridge, BridgePortState, BridgeSlave, Dummy, FAMILY_BRIDGE, Geneve, Gre, Inet6DevConf, InetDevConf, Iptun, Ipvlan, IpvlanFlag, IpvlanMode, Ipvtap, LinkInet, LinkInet6, LinkSlave, Macvlan, MacvlanMode, Macvtap, TunnelDf, TunnelEncap, TunnelEncapType, Tuntap, Veth, Vlan, VlanProtocol, Vrf, Vxlan, Wireguard};
 
 pub type Stats = Stats64;
 pub type OperState = State;
@@ -37,7 +41,6 @@ pub struct LinkAttrs {
     pub multi: i32,
     // xdp: Option<LinkXdp>,
     pub encap_type: String,
-    // protinfo: Option<Protinfo>,
     pub oper_state: OperState,
     pub phys_switch_id: u32,
     pub net_ns_id: i32,
@@ -47,7 +50,9 @@ pub struct LinkAttrs {
     pub gso_max_segs: u32,
     // vfs: Vec<u8>,
     pub group: u32,
-    // slave: LinkSlave,
+    pub slave: Option<LinkSlave>,
+    pub inet: Option<LinkInet>,
+    pub inet6: Option<LinkInet6>,
 }
 
 impl LinkAttrs {
@@ -83,6 +88,9 @@ impl Default for LinkAttrs {
             gso_max_size: 0,
             gso_max_segs: 0,
             group: 0,
+            slave: None,
+            inet: None,
+            inet6: None,
         }
     }
 }
@@ -127,6 +135,23 @@ pub enum LinkKind {
     Bridge(Bridge),
     Tuntap(Tuntap),
     Vxlan(Vxlan),
+    Vlan(Vlan),
+    Macvlan(Macvlan),
+    Macvtap(Macvtap),
+    Ipvlan(Ipvlan),
+    Ipvtap(Ipvtap),
+    Bond(Bond),
+    Gre(Gre),
+    Gretap(Gre),
+    Ip6gre(Gre),
+    Ip6gretap(Gre),
+    Erspan(Gre),
+    Ipip(Iptun),
+    Sit(Iptun),
+    Ip6tnl(Iptun),
+    Geneve(Geneve),
+    Vrf(Vrf),
+    Wireguard(Wireguard),
     Device,
     Dummy(Dummy),
 }
@@ -138,6 +163,23 @@ impl LinkKind {
             LinkKind::Bridge(_) => "bridge",
             LinkKind::Tuntap(_) => "tuntap",
             LinkKind::Vxlan(_) => "vxlan",
+            LinkKind::Vlan(_) => "vlan",
+            LinkKind::Macvlan(_) => "macvlan",
+            LinkKind::Macvtap(_) => "macvtap",
+            LinkKind::Ipvlan(_) => "ipvlan",
+            LinkKind::Ipvtap(_) => "ipvtap",
+            LinkKind::Bond(_) => "bond",
+            LinkKind::Gre(_) => "gre",
+            LinkKind::Gretap(_) => "gretap",
+            LinkKind::Ip6gre(_) => "ip6gre",
+            LinkKind::Ip6gretap(_) => "ip6gretap",
+            LinkKind::Erspan(_) => "erspan",
+            LinkKind::Ipip(_) => "ipip",
+            LinkKind::Sit(_) => "sit",
+            LinkKind::Ip6tnl(_) => "ip6tnl",
+            LinkKind::Geneve(_) => "geneve",
+            LinkKind::Vrf(_) => "vrf",
+            LinkKind::Wireguard(_) => "wireguard",
             LinkKind::Dummy(_) => "dummy",
             LinkKind::Device => "device",
         }
@@ -154,10 +196,79 @@ impl Link {
     }
 }
 
+// LinkResponse reads link replies like RtnlMessage does, except that the IFLA_INFO_DATA
+// of vxlan links is kept raw as InfoData::Other: the library fails on the empty vxlan
+// flags (gbp, gpe, remcsum_nopartial) and loses the attributes it does not know.
+#[derive(Debug)]
+struct LinkResponse(RtnlMessage);
+
+impl NetlinkSerializable for LinkResponse {
+    fn message_type(&self) -> u16 {
+        self.0.message_type()
+    }
+
+    fn buffer_len(&self) -> usize {
+        NetlinkSerializable::buffer_len(&self.0)
+    }
+
+    fn serialize(&self, buffer: &mut [u8]) {
+        self.0.serialize(buffer)
+    }
+}
+
+impl NetlinkDeserializable for LinkResponse {
+    type Error = DecodeError;
+
+    fn deserialize(header: &NetlinkHeader, payload: &[u8]) -> Result<Self, Self::Error> {
+        if header.message_type != RTM_NEWLINK {
+            return RtnlMessage::deserialize(header, payload).map(LinkResponse);
+        }
+        let buf = LinkMessageBuffer::new_checked(payload)?;
+        let mut msg = LinkMessage::default();
+        msg.header = LinkHeader::parse(&buf)?;
+        for nla in buf.nlas() {
+            let nla = nla?;
+            let parsed = match nla.kind() {
+                IFLA_LINKINFO => link_info_parse(nla.value(), msg.header.interface_family)?,
+                _ => Nla::parse_with_param(&nla, msg.header.interface_family as u16)?,
+            };
+            msg.nlas.push(parsed);
+        }
+        Ok(LinkResponse(RtnlMessage::NewLink(msg)))
+    }
+}
+
+impl From<LinkResponse> for NetlinkPayload<LinkResponse> {
+    fn from(msg: LinkResponse) -> Self {
+        NetlinkPayload::InnerMessage(msg)
+    }
+}
+
+// link_info_parse parses IFLA_LINKINFO with the library, setting the IFLA_INFO_DATA of
+// vxlan links aside to return it as InfoData::Other
+fn link_info_parse(payload: &[u8], family: u8) -> Result<Nla, DecodeError> {
+    let nlas: Vec<DefaultNla> = utils::parse_nlas(payload);
+    let is_vxlan = nlas.iter()
+        .any(|nla| nla.kind() == IFLA_INFO_KIND && utils::default_nla_value(nla).starts_with(b"vxlan\0"));
+    let data = nlas.iter()
+        .find(|nla| nla.kind() == IFLA_INFO_DATA)
+        .map(utils::default_nla_value)
+        .filter(|_| is_vxlan);
+    let nlas: Vec<_> = nlas.into_iter()
+        .filter(|nla| data.is_none() || nla.kind() != IFLA_INFO_DATA)
+        .collect();
+    let link_info = utils::emit_nlas(&[DefaultNla::new(IFLA_LINKINFO, utils::emit_nlas(&nlas))]);
+    let mut parsed = Nla::parse_with_param(&NlaBuffer::new_checked(&link_info)?, family as u16)?;
+    if let (Nla::Info(infos), Some(data)) = (&mut parsed, data) {
+        infos.push(Info::Data(InfoData::Other(data)));
+    }
+    Ok(parsed)
+}
+
 pub fn link_by_index(index: u32) -> anyhow::Result<Option<Link>> {
     let mut msg = LinkMessage::default();
     msg.header.index = index;
-    let resp = NetlinkHandle::new().execute(RtnlMessage::GetLink(msg), NLM_F_ACK)?;
+    let resp = NetlinkHandle::new().execute(LinkResponse(RtnlMessage::GetLink(msg)), NLM_F_ACK)?;
     if resp.len() == 0 {
         return Ok(None);
     }
@@ -166,7 +277,7 @@ pub fn link_by_index(index: u32) -> anyhow::Result<Option<Link>> {
     }
 
     let resp_msg = resp.first().unwrap();
-    let link = link_deserialize(resp_msg)?;
+    let link = link_deserialize(&resp_msg.0)?;
     Ok(Some(link))
 }
 
@@ -176,7 +287,7 @@ pub fn link_by_name(name: &str) -> anyhow::Result<Option<Link>> {
     msg.nlas.push(Nla::ExtMask(RTEXT_FILTER_VF));
     msg.nlas.push(Nla::IfName(name.to_string()));
 
-    let resp = NetlinkHandle::new().execute(RtnlMessage::GetLink(msg), NLM_F_ACK)?;
+    let resp = NetlinkHandle::new().execute(LinkResponse(RtnlMessage::GetLink(msg)), NLM_F_ACK)?;
     if resp.len() == 0 {
         return Ok(None);
     }
@@ -185,10 +296,12 @@ pub fn link_by_name(name: &str) -> anyhow::Result<Option<Link>> {
     }
 
     let resp_msg = resp.first().unwrap();
-    let link = link_deserialize(resp_msg)?;
+    let link = link_deserialize(&resp_msg.0)?;
     Ok(Some(link))
 }
 
+// link_add creates a link, tuntap links are created with tuntap_add instead.
+// Equivalent to: `ip link add $link`
 pub fn link_add(link: &Link) -> anyhow::Result<()> {
     link_modify(link, NLM_F_CREATE | NLM_F_EXCL | NLM_F_ACK)
 }
@@ -199,9 +312,9 @@ pub fn link_list() -> anyhow::Result<Vec<Link>> {
     msg.nlas.push(Nla::ExtMask(RTEXT_FILTER_VF));
 
     let res = NetlinkHandle::new()
-        .execute(RtnlMessage::GetLink(msg), NLM_F_ACK | NLM_F_DUMP)?
+        .execute(LinkResponse(RtnlMessage::GetLink(msg)), NLM_F_ACK | NLM_F_DUMP)?
         .iter()
-        .map(|it| link_deserialize(it))
+        .map(|it| link_deserialize(&it.0))
         .collect::<Vec<_>>();
     let res: Result<Vec<_>, _> = res.into_iter().collect();
     let links: Vec<Link> = res?;
@@ -327,7 +440,7 @@ fn link_modify(link: &Link, flags: u16) -> anyhow::Result<()> {
         msg.header.index = base.index;
     }
     if base.parent_index != 0 {
-        // todo
+        msg.nlas.push(Nla::Link(base.parent_index));
     }
 
     msg.nlas.push(Nla::IfName(base.name.clone()));
@@ -405,38 +518,73 @@ fn link_modify(link: &Link, flags: u16) -> anyhow::Result<()> {
             link_info_nlas.push(Info::Data(InfoData::Veth(VethInfo::Peer(peer))));
             msg.nlas.push(Nla::Info(link_info_nlas));
         }
-        LinkKind::Bridge(Bridge {
-                             multicast_snooping,
-                             ageing_time,
-                             hello_time: _hello_time,
-                             vlan_filtering,
-                             vlan_filtering_pvid,
-                         }) => {
-            let mut vec = Vec::new();
-            if let Some(v) = multicast_snooping {
-                vec.push(InfoBridge::MulticastSnooping(*v as u8));
-            }
-            if let Some(v) = ageing_time {
-                vec.push(InfoBridge::AgeingTime(*v));
-            }
-            if let Some(v) = vlan_filtering {
-                vec.push(InfoBridge::VlanFiltering(*v as u8));
-            }
-            if let Some(v) = vlan_filtering_pvid {
-                vec.push(InfoBridge::VlanDefaultPvid(*v));
-            }
+        LinkKind::Bridge(bridge) => {
+            let vec = bridge_nlas(bridge);
             if !vec.is_empty() {
                 link_info_nlas.push(Info::Data(InfoData::Bridge(vec)));
             }
             msg.nlas.push(Nla::Info(link_info_nlas));
         }
         LinkKind::Device => {
-            unimplemented!()
+            bail!("cannot create a link of kind device");
+        }
+        LinkKind::Tuntap(_) => {
+            bail!("tuntap links can't be created over netlink, use tuntap_add");
+        }
+        LinkKind::Dummy(_) | LinkKind::Wireguard(_) => {
+            msg.nlas.push(Nla::Info(link_info_nlas));
         }
-        LinkKind::Tuntap(_) => {}
-        LinkKind::Dummy(_) => {}
         LinkKind::Vxlan(vxlan) => {
-            add_vxlan_attrs(&mut msg, link_info_nlas, vxlan);
+            link_info_nlas.push(Info::Data(InfoData::Other(utils::emit_nlas(&vxlan_nlas(vxlan)))));
+            msg.nlas.push(Nla::Info(link_info_nlas));
+        }
+        LinkKind::Vlan(vlan) => {
+            add_vlan_attrs(&mut msg, link_info_nlas, vlan);
+        }
+        LinkKind::Macvlan(macvlan) => {
+            link_info_nlas.push(Info::Data(InfoData::MacVlan(macvlan_nlas(macvlan))));
+            msg.nlas.push(Nla::Info(link_info_nlas));
+        }
+        LinkKind::Macvtap(macvtap) => {
+            link_info_nlas.push(Info::Data(InfoData::MacVtap(convert_macvlan_nlas(&macvlan_nlas(macvtap)))));
+            msg.nlas.push(Nla::Info(link_info_nlas));
+        }
+        LinkKind::Ipvlan(ipvlan) => {
+            link_info_nlas.push(Info::Data(InfoData::IpVlan(ipvlan_nlas(ipvlan))));
+            msg.nlas.push(Nla::Info(link_info_nlas));
+        }
+        LinkKind::Ipvtap(ipvtap) => {
+            // the library has no ipvtap kind, its data is the same as ipvlan's
+            link_info_nlas.push(Info::Data(InfoData::Other(utils::emit_nlas(&ipvlan_nlas(ipvtap)))));
+            msg.nlas.push(Nla::Info(link_info_nlas));
+        }
+        LinkKind::Bond(bond) => {
+            let nlas = bond_nlas(bond);
+            if !nlas.is_empty() {
+                link_info_nlas.push(Info::Data(InfoData::Bond(nlas)));
+            }
+            msg.nlas.push(Nla::Info(link_info_nlas));
+        }
+        LinkKind::Gre(gre) | LinkKind::Gretap(gre) | LinkKind::Erspan(gre) => {
+            link_info_nlas.push(Info::Data(InfoData::Other(utils::emit_nlas(&gre_nlas(gre, false)))));
+            msg.nlas.push(Nla::Info(link_info_nlas));
+        }
+        LinkKind::Ip6gre(gre) | LinkKind::Ip6gretap(gre) => {
+            link_info_nlas.push(Info::Data(InfoData::Other(utils::emit_nlas(&gre_nlas(gre, true)))));
+            msg.nlas.push(Nla::Info(link_info_nlas));
+        }
+        LinkKind::Ipip(iptun) | LinkKind::Sit(iptun) | LinkKind::Ip6tnl(iptun) => {
+            let nlas = iptun_nlas(iptun, &link.link_kind);
+            link_info_nlas.push(Info::Data(InfoData::Other(utils::emit_nlas(&nlas))));
+            msg.nlas.push(Nla::Info(link_info_nlas));
+        }
+        LinkKind::Geneve(geneve) => {
+            link_info_nlas.push(Info::Data(InfoData::Other(utils::emit_nlas(&geneve_nlas(geneve)))));
+            msg.nlas.push(Nla::Info(link_info_nlas));
+        }
+        LinkKind::Vrf(vrf) => {
+            link_info_nlas.push(Info::Data(InfoData::Vrf(vec![InfoVrf::TableId(vrf.table)])));
+            msg.nlas.push(Nla::Info(link_info_nlas));
         }
     }
     let _ = NetlinkHandle::new().execute(RtnlMessage::NewLink(msg), flags)?;
@@ -444,239 +592,1352 @@ fn link_modify(link: &Link, flags: u16) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn add_vxlan_attrs(msg: &mut LinkMessage, mut link_info_nlas: Vec<Info>, vxlan: &Vxlan) {
-    let mut vec = vec![
-        InfoVxlan::Id(vxlan.vxlan_id)
-    ];
-    if vxlan.vtep_dev_index > 0 {
-        vec.push(InfoVxlan::Link(vxlan.vtep_dev_index));
+fn bridge_nlas(bridge: &Bridge) -> Vec<InfoBridge> {
+    let mut vec = Vec::new();
+    if let Some(v) = bridge.multicast_snooping {
+        vec.push(InfoBridge::MulticastSnooping(v as u8));
     }
-
-    if let Some(src_addr) = vxlan.src_addr {
-        if src_addr.is_ipv4() {
-            vec.push(InfoVxlan::Local(utils::ip_to_bytes(&src_addr)));
-        } else {
-            vec.push(InfoVxlan::Local6(utils::ip_to_bytes(&src_addr)));
-        }
+    if let Some(v) = bridge.vlan_filtering {
+        vec.push(InfoBridge::VlanFiltering(v as u8));
     }
-    if let Some(group) = vxlan.group {
-        if group.is_ipv4() {
-            vec.push(InfoVxlan::Group(utils::ip_to_bytes(&group)));
-        } else {
-            vec.push(InfoVxlan::Group6(utils::ip_to_bytes(&group)));
-        }
+    if let Some(v) = bridge.vlan_stats_enabled {
+        vec.push(InfoBridge::VlanStatsEnabled(v as u8));
     }
-    if vxlan.ttl > 0 {
-        vec.push(InfoVxlan::Ttl(vxlan.ttl as u8));
+    if let Some(v) = bridge.vlan_stats_per_host {
+        vec.push(InfoBridge::VlanStatsPerHost(v as u8));
     }
-    if vxlan.tos > 0 {
-        vec.push(InfoVxlan::Tos(vxlan.tos as u8));
+    if let Some(v) = bridge.multicast_querier {
+        vec.push(InfoBridge::MulticastQuerier(v as u8));
+    }
+    if let Some(v) = bridge.multicast_query_use_ifaddr {
+        vec.push(InfoBridge::MulticastQueryUseIfaddr(v as u8));
+    }
+    if let Some(v) = bridge.multicast_stats_enabled {
+        vec.push(InfoBridge::MulticastStatsEnabled(v as u8));
+    }
+    if let Some(v) = bridge.nf_call_iptables {
+        vec.push(InfoBridge::NfCallIpTables(v as u8));
     }
-    if vxlan.learning {
-        vec.push(InfoVxlan::Learning(1));
+    if let Some(v) = bridge.nf_call_ip6tables {
+        vec.push(InfoBridge::NfCallIp6Tables(v as u8));
     }
-    if vxlan.proxy {
-        vec.push(InfoVxlan::Proxy(1));
+    if let Some(v) = bridge.nf_call_arptables {
+        vec.push(InfoBridge::NfCallArpTables(v as u8));
     }
-    if vxlan.rsc {
-        vec.push(InfoVxlan::Rsc(1));
+    if let Some(v) = bridge.ageing_time {
+        vec.push(InfoBridge::AgeingTime(v));
     }
-    if vxlan.l2miss {
-        vec.push(InfoVxlan::L2Miss(1));
+    if let Some(v) = bridge.hello_time {
+        vec.push(InfoBridge::HelloTime(v));
     }
-    if vxlan.l3miss {
-        vec.push(InfoVxlan::L3Miss(1));
+    if let Some(v) = bridge.stp_state {
+        vec.push(InfoBridge::StpState(v));
     }
-    if vxlan.udp6_zero_csum_tx {
-        vec.push(InfoVxlan::UDPZeroCsumTX(1));
+    if let Some(v) = bridge.forward_delay {
+        vec.push(InfoBridge::ForwardDelay(v));
     }
-    if vxlan.udp6_zero_csum_rx {
-        vec.push(InfoVxlan::UDPZeroCsumRX(1));
+    if let Some(v) = bridge.max_age {
+        vec.push(InfoBridge::MaxAge(v));
     }
-    if vxlan.udp_csum {
-        vec.push(InfoVxlan::UDPCsum(1));
+    if let Some(v) = bridge.multicast_hash_max {
+        vec.push(InfoBridge::MulticastHashMax(v));
     }
-    if vxlan.gbp {
-        vec.push(InfoVxlan::Gbp(1));
+    if let Some(v) = bridge.multicast_last_member_count {
+        vec.push(InfoBridge::MulticastLastMemberCount(v));
+    }
+    if let Some(v) = bridge.multicast_startup_query_count {
+        vec.push(InfoBridge::MulticastStartupQueryCount(v));
+    }
+    if let Some(v) = bridge.multicast_last_member_interval {
+        vec.push(InfoBridge::MulticastLastMemberInterval(v));
+    }
+    if let Some(v) = bridge.multicast_membership_interval {
+        vec.push(InfoBridge::MulticastMembershipInterval(v));
+    }
+    if let Some(v) = bridge.multicast_querier_interval {
+        vec.push(InfoBridge::MulticastQuerierInterval(v));
+    }
+    if let Some(v) = bridge.multicast_query_interval {
+        vec.push(InfoBridge::MulticastQueryInterval(v));
+    }
+    if let Some(v) = bridge.multicast_query_response_interval {
+        vec.push(InfoBridge::MulticastQueryResponseInterval(v));
+    }
+    if let Some(v) = bridge.multicast_startup_query_interval {
+        vec.push(InfoBridge::MulticastStartupQueryInterval(v));
+    }
+    if let Some(v) = bridge.vlan_filtering_pvid {
+        vec.push(InfoBridge::VlanDefaultPvid(v));
+    }
+    if let Some(v) = bridge.priority {
+        vec.push(InfoBridge::Priority(v));
+    }
+    if let Some(v) = bridge.group_fwd_mask {
+        vec.push(InfoBridge::GroupFwdMask(v));
+    }
+    if let Some(v) = bridge.group_addr {
+        vec.push(InfoBridge::GroupAddr(v.into_array()));
+    }
+    if let Some(v) = bridge.vlan_protocol {
+        vec.push(InfoBridge::VlanProtocol(v.into()));
+    }
+    if let Some(v) = bridge.multicast_router {
+        vec.push(InfoBridge::MulticastRouter(v));
+    }
+    if let Some(v) = bridge.multicast_igmp_version {
+        vec.push(InfoBridge::MulticastIgmpVersion(v));
+    }
+    if let Some(v) = bridge.multicast_mld_version {
+        vec.push(InfoBridge::MulticastMldVersion(v));
+    }
+    vec
+}
+
+fn bridge_deserialize(nlas: &[InfoBridge]) -> Bridge {
+    let mut bridge = Bridge::default();
+    for nla in nlas {
+        match nla {
+            InfoBridge::MulticastSnooping(v) => bridge.multicast_snooping = Some(*v != 0),
+            InfoBridge::AgeingTime(v) => bridge.ageing_time = Some(*v),
+            InfoBridge::HelloTime(v) => bridge.hello_time = Some(*v),
+            InfoBridge::VlanFiltering(v) => bridge.vlan_filtering = Some(*v != 0),
+            InfoBridge::VlanDefaultPvid(v) => bridge.vlan_filtering_pvid = Some(*v),
+            InfoBridge::StpState(v) => bridge.stp_state = Some(*v),
+            InfoBridge::ForwardDelay(v) => bridge.forward_delay = Some(*v),
+            InfoBridge::MaxAge(v) => bridge.max_age = Some(*v),
+            InfoBridge::Priority(v) => bridge.priority = Some(*v),
+            InfoBridge::GroupFwdMask(v) => bridge.group_fwd_mask = Some(*v),
+            InfoBridge::GroupAddr(v) => bridge.group_addr = Some(MacAddr6::from(*v)),
+            InfoBridge::VlanProtocol(v) => bridge.vlan_protocol = VlanProtocol::try_from(*v).ok(),
+            InfoBridge::VlanStatsEnabled(v) => bridge.vlan_stats_enabled = Some(*v != 0),
+            InfoBridge::VlanStatsPerHost(v) => bridge.vlan_stats_per_host = Some(*v != 0),
+            InfoBridge::MulticastRouter(v) => bridge.multicast_router = Some(*v),
+            InfoBridge::MulticastQuerier(v) => bridge.multicast_querier = Some(*v != 0),
+            InfoBridge::MulticastQueryUseIfaddr(v) => bridge.multicast_query_use_ifaddr = Some(*v != 0),
+            InfoBridge::MulticastStatsEnabled(v) => bridge.multicast_stats_enabled = Some(*v != 0),
+            InfoBridge::MulticastIgmpVersion(v) => bridge.multicast_igmp_version = Some(*v),
+            InfoBridge::MulticastMldVersion(v) => bridge.multicast_mld_version = Some(*v),
+            InfoBridge::MulticastHashMax(v) => bridge.multicast_hash_max = Some(*v),
+            InfoBridge::MulticastLastMemberCount(v) => bridge.multicast_last_member_count = Some(*v),
+            InfoBridge::MulticastStartupQueryCount(v) => bridge.multicast_startup_query_count = Some(*v),
+            InfoBridge::MulticastLastMemberInterval(v) => bridge.multicast_last_member_interval = Some(*v),
+            InfoBridge::MulticastMembershipInterval(v) => bridge.multicast_membership_interval = Some(*v),
+            InfoBridge::MulticastQuerierInterval(v) => bridge.multicast_querier_interval = Some(*v),
+            InfoBridge::MulticastQueryInterval(v) => bridge.multicast_query_interval = Some(*v),
+            InfoBridge::MulticastQueryResponseInterval(v) => bridge.multicast_query_response_interval = Some(*v),
+            InfoBridge::MulticastStartupQueryInterval(v) => bridge.multicast_startup_query_interval = Some(*v),
+            InfoBridge::NfCallIpTables(v) => bridge.nf_call_iptables = Some(*v != 0),
+            InfoBridge::NfCallIp6Tables(v) => bridge.nf_call_ip6tables = Some(*v != 0),
+            InfoBridge::NfCallArpTables(v) => bridge.nf_call_arptables = Some(*v != 0),
+            _ => {}
+        }
+    }
+    bridge
+}
+
+// vxlan attributes are emitted raw, the flag attributes (gbp, gpe, ...) carry no payload
+// and IFLA_VXLAN_VNIFILTER is unknown to netlink-packet-route
+fn vxlan_nlas(vxlan: &Vxlan) -> Vec<DefaultNla> {
+    let mut vec = vec![
+        nla_u32(IFLA_VXLAN_ID, vxlan.vxlan_id),
+    ];
+    if vxlan.vtep_dev_index > 0 {
+        vec.push(nla_u32(IFLA_VXLAN_LINK, vxlan.vtep_dev_index));
+    }
+
+    if let Some(src_addr) = &vxlan.src_addr {
+        vec.push(nla_ip(if src_addr.is_ipv4() { IFLA_VXLAN_LOCAL } else { IFLA_VXLAN_LOCAL6 }, src_addr));
+    }
+    if let Some(group) = &vxlan.group {
+        vec.push(nla_ip(if group.is_ipv4() { IFLA_VXLAN_GROUP } else { IFLA_VXLAN_GROUP6 }, group));
+    }
+    if vxlan.ttl > 0 {
+        vec.push(nla_u8(IFLA_VXLAN_TTL, vxlan.ttl as u8));
+    }
+    if vxlan.tos > 0 {
+        vec.push(nla_u8(IFLA_VXLAN_TOS, vxlan.tos as u8));
+    }
+    // the kernel enables learning and disables the udp checksum when these are missing
+    vec.push(nla_u8(IFLA_VXLAN_LEARNING, vxlan.learning as u8));
+    vec.push(nla_u8(IFLA_VXLAN_PROXY, vxlan.proxy as u8));
+    vec.push(nla_u8(IFLA_VXLAN_RSC, vxlan.rsc as u8));
+    vec.push(nla_u8(IFLA_VXLAN_L2MISS, vxlan.l2miss as u8));
+    vec.push(nla_u8(IFLA_VXLAN_L3MISS, vxlan.l3miss as u8));
+    vec.push(nla_u8(IFLA_VXLAN_UDP_CSUM, vxlan.udp_csum as u8));
+    vec.push(nla_u8(IFLA_VXLAN_UDP_ZERO_CSUM6_TX, vxlan.udp6_zero_csum_tx as u8));
+    vec.push(nla_u8(IFLA_VXLAN_UDP_ZERO_CSUM6_RX, vxlan.udp6_zero_csum_rx as u8));
+    if vxlan.remcsum_tx {
+        vec.push(nla_u8(IFLA_VXLAN_REMCSUM_TX, 1));
+    }
+    if vxlan.remcsum_rx {
+        vec.push(nla_u8(IFLA_VXLAN_REMCSUM_RX, 1));
+    }
+    for (kind, enabled) in [
+        (IFLA_VXLAN_GBP, vxlan.gbp),
+        (IFLA_VXLAN_GPE, vxlan.gpe),
+        (IFLA_VXLAN_REMCSUM_NOPARTIAL, vxlan.remcsum_nopartial),
+        (IFLA_VXLAN_TTL_INHERIT, vxlan.ttl_inherit),
+    ] {
+        if enabled {
+            vec.push(DefaultNla::new(kind, vec![]));
+        }
     }
 
     if vxlan.flow_based {
-        // todo
+        vec.push(nla_u8(IFLA_VXLAN_COLLECT_METADATA, 1));
+    }
+    if vxlan.vni_filter {
+        vec.push(nla_u8(IFLA_VXLAN_VNIFILTER, 1));
     }
     if vxlan.no_age {
-        vec.push(InfoVxlan::Ageing(0));
+        vec.push(nla_u32(IFLA_VXLAN_AGEING, 0));
     } else if vxlan.age > 0 {
-        vec.push(InfoVxlan::Ageing(vxlan.age));
+        vec.push(nla_u32(IFLA_VXLAN_AGEING, vxlan.age));
     }
 
     if vxlan.limit > 0 {
-        vec.push(InfoVxlan::Limit(vxlan.limit));
+        vec.push(nla_u32(IFLA_VXLAN_LIMIT, vxlan.limit));
     }
+    // ports and the label are big endian
     if vxlan.port > 0 {
-        vec.push(InfoVxlan::Port(vxlan.port));
+        vec.push(DefaultNla::new(IFLA_VXLAN_PORT, vxlan.port.to_be_bytes().to_vec()));
     }
     if vxlan.port_low > 0 && vxlan.port_high > 0 {
-        vec.push(InfoVxlan::PortRange((vxlan.port_low, vxlan.port_high)));
+        let range = [vxlan.port_low.to_be_bytes(), vxlan.port_high.to_be_bytes()].concat();
+        vec.push(DefaultNla::new(IFLA_VXLAN_PORT_RANGE, range));
     }
-    link_info_nlas.push(Info::Data(InfoData::Vxlan(vec)));
-    msg.nlas.push(Nla::Info(link_info_nlas));
+    if vxlan.label > 0 {
+        vec.push(nla_u32(IFLA_VXLAN_LABEL, vxlan.label.to_be()));
+    }
+    if vxlan.df != TunnelDf::Unset {
+        vec.push(nla_u8(IFLA_VXLAN_DF, vxlan.df.into()));
+    }
+    vec
 }
 
-// LinkSetMaster sets the master of the link device.
-// Equivalent to: `ip link set $link master $master`
-pub fn link_set_master(link: &Link, master: &Link) -> anyhow::Result<()> {
-    let master_index = master.link_attrs.index;
-    link_set_master_by_index(link, master_index)
+fn vxlan_deserialize(nlas: &[DefaultNla]) -> Vxlan {
+    let mut vxlan = Vxlan::default();
+    for nla in nlas {
+        let value = utils::default_nla_value(nla);
+        let enabled = utils::default_nla_u8(nla).unwrap_or_default() != 0;
+        match nla.kind() {
+            IFLA_VXLAN_ID => vxlan.vxlan_id = utils::default_nla_u32(nla).unwrap_or_default(),
+            IFLA_VXLAN_LINK => vxlan.vtep_dev_index = utils::default_nla_u32(nla).unwrap_or_default(),
+            IFLA_VXLAN_LOCAL | IFLA_VXLAN_LOCAL6 => vxlan.src_addr = utils::bytes_to_ip_by_len(&value).ok(),
+            IFLA_VXLAN_GROUP | IFLA_VXLAN_GROUP6 => vxlan.group = utils::bytes_to_ip_by_len(&value).ok(),
+            IFLA_VXLAN_TTL => vxlan.ttl = utils::default_nla_u8(nla).unwrap_or_default() as i32,
+            IFLA_VXLAN_TOS => vxlan.tos = utils::default_nla_u8(nla).unwrap_or_default() as i32,
+            IFLA_VXLAN_LEARNING => vxlan.learning = enabled,
+            IFLA_VXLAN_PROXY => vxlan.proxy = enabled,
+            IFLA_VXLAN_RSC => vxlan.rsc = enabled,
+            IFLA_VXLAN_L2MISS => vxlan.l2miss = enabled,
+            IFLA_VXLAN_L3MISS => vxlan.l3miss = enabled,
+            IFLA_VXLAN_UDP_CSUM => vxlan.udp_csum = enabled,
+            IFLA_VXLAN_UDP_ZERO_CSUM6_TX => vxlan.udp6_zero_csum_tx = enabled,
+            IFLA_VXLAN_UDP_ZERO_CSUM6_RX => vxlan.udp6_zero_csum_rx = enabled,
+            IFLA_VXLAN_REMCSUM_TX => vxlan.remcsum_tx = enabled,
+            IFLA_VXLAN_REMCSUM_RX => vxlan.remcsum_rx = enabled,
+            IFLA_VXLAN_GBP => vxlan.gbp = true,
+            IFLA_VXLAN_GPE => vxlan.gpe = true,
+            IFLA_VXLAN_REMCSUM_NOPARTIAL => vxlan.remcsum_nopartial = true,
+            IFLA_VXLAN_TTL_INHERIT => vxlan.ttl_inherit = enabled,
+            IFLA_VXLAN_COLLECT_METADATA => vxlan.flow_based = enabled,
+            IFLA_VXLAN_VNIFILTER => vxlan.vni_filter = enabled,
+            IFLA_VXLAN_AGEING => {
+                vxlan.age = utils::default_nla_u32(nla).unwrap_or_default();
+                vxlan.no_age = vxlan.age == 0;
+            }
+            IFLA_VXLAN_LIMIT => vxlan.limit = utils::default_nla_u32(nla).unwrap_or_default(),
+            IFLA_VXLAN_PORT => vxlan.port = u16::from_be(utils::default_nla_u16(nla).unwrap_or_default()),
+            IFLA_VXLAN_PORT_RANGE => {
+                if let [l0, l1, h0, h1] = value[..] {
+                    vxlan.port_low = u16::from_be_bytes([l0, l1]);
+                    vxlan.port_high = u16::from_be_bytes([h0, h1]);
+                }
+            }
+            IFLA_VXLAN_LABEL => vxlan.label = u32::from_be(utils::default_nla_u32(nla).unwrap_or_default()),
+            IFLA_VXLAN_DF => vxlan.df = utils::default_nla_u8(nla).and_then(|df| df.try_into().ok()).unwrap_or_default(),
+            _ => {}
+        }
+    }
+    vxlan
 }
 
-fn link_set_master_by_index(link: &Link, master_index: u32) -> anyhow::Result<()> {
-    let mut msg = LinkMessage::default();
-    msg.header.index = link.attrs().index;
-    msg.nlas.push(Nla::Master(master_index));
-    let _ = NetlinkHandle::new().execute(RtnlMessage::SetLink(msg), NLM_F_ACK)?;
-    Ok(())
+fn geneve_nlas(geneve: &Geneve) -> Vec<DefaultNla> {
+    let mut vec = vec![
+        nla_u32(IFLA_GENEVE_ID, geneve.id),
+    ];
+    if let Some(remote) = &geneve.remote {
+        vec.push(nla_ip(if remote.is_ipv4() { IFLA_GENEVE_REMOTE } else { IFLA_GENEVE_REMOTE6 }, remote));
+    }
+    if geneve.ttl_inherit {
+        vec.push(nla_u8(IFLA_GENEVE_TTL_INHERIT, 1));
+    } else if geneve.ttl > 0 {
+        vec.push(nla_u8(IFLA_GENEVE_TTL, geneve.ttl));
+    }
+    if geneve.tos > 0 {
+        vec.push(nla_u8(IFLA_GENEVE_TOS, geneve.tos));
+    }
+    // the port and the label are big endian
+    if geneve.label > 0 {
+        vec.push(nla_u32(IFLA_GENEVE_LABEL, geneve.label.to_be()));
+    }
+    if geneve.port > 0 {
+        vec.push(DefaultNla::new(IFLA_GENEVE_PORT, geneve.port.to_be_bytes().to_vec()));
+    }
+    if geneve.collect_metadata {
+        vec.push(DefaultNla::new(IFLA_GENEVE_COLLECT_METADATA, vec![]));
+    }
+    if geneve.udp_csum {
+        vec.push(nla_u8(IFLA_GENEVE_UDP_CSUM, 1));
+    }
+    if geneve.udp6_zero_csum_tx {
+        vec.push(nla_u8(IFLA_GENEVE_UDP_ZERO_CSUM6_TX, 1));
+    }
+    if geneve.udp6_zero_csum_rx {
+        vec.push(nla_u8(IFLA_GENEVE_UDP_ZERO_CSUM6_RX, 1));
+    }
+    if geneve.df != TunnelDf::Unset {
+        vec.push(nla_u8(IFLA_GENEVE_DF, geneve.df.into()));
+    }
+    if geneve.inner_proto_inherit {
+        vec.push(DefaultNla::new(IFLA_GENEVE_INNER_PROTO_INHERIT, vec![]));
+    }
+    vec
 }
 
-
-fn get_link_kind(link: &LinkKind) -> InfoKind {
-    match link {
-        LinkKind::Veth { .. } => InfoKind::Veth,
-        LinkKind::Bridge { .. } => InfoKind::Bridge,
-        LinkKind::Device { .. } => InfoKind::Dummy,
-        LinkKind::Tuntap { .. } => InfoKind::IpTun,
-        LinkKind::Dummy { .. } => { InfoKind::Dummy }
-        LinkKind::Vxlan(_) => { InfoKind::Vxlan }
+fn geneve_deserialize(nlas: &[DefaultNla]) -> Geneve {
+    let mut geneve = Geneve::default();
+    for nla in nlas {
+        let value = utils::default_nla_value(nla);
+        let enabled = utils::default_nla_u8(nla).unwrap_or_default() != 0;
+        match nla.kind() {
+            IFLA_GENEVE_ID => geneve.id = utils::default_nla_u32(nla).unwrap_or_default(),
+            IFLA_GENEVE_REMOTE | IFLA_GENEVE_REMOTE6 => geneve.remote = utils::bytes_to_ip_by_len(&value).ok().filter(|ip| !ip.is_unspecified()),
+            IFLA_GENEVE_TTL => geneve.ttl = utils::default_nla_u8(nla).unwrap_or_default(),
+            IFLA_GENEVE_TTL_INHERIT => geneve.ttl_inherit = enabled,
+            IFLA_GENEVE_TOS => geneve.tos = utils::default_nla_u8(nla).unwrap_or_default(),
+            IFLA_GENEVE_LABEL => geneve.label = u32::from_be(utils::default_nla_u32(nla).unwrap_or_default()),
+            IFLA_GENEVE_PORT => geneve.port = u16::from_be(utils::default_nla_u16(nla).unwrap_or_default()),
+            IFLA_GENEVE_COLLECT_METADATA => geneve.collect_metadata = true,
+            IFLA_GENEVE_UDP_CSUM => geneve.udp_csum = enabled,
+            IFLA_GENEVE_UDP_ZERO_CSUM6_TX => geneve.udp6_zero_csum_tx = enabled,
+            IFLA_GENEVE_UDP_ZERO_CSUM6_RX => geneve.udp6_zero_csum_rx = enabled,
+            IFLA_GENEVE_DF => geneve.df = utils::default_nla_u8(nla).and_then(|df| df.try_into().ok()).unwrap_or_default(),
+            IFLA_GENEVE_INNER_PROTO_INHERIT => geneve.inner_proto_inherit = true,
+            _ => {}
+        }
     }
+    geneve
 }
 
-
-fn link_deserialize(msg: &RtnlMessage) -> anyhow::Result<Link> {
-    let index = rtnl_msg_ext::index(&msg);
-    let flags = rtnl_msg_ext::flags(&msg);
-    let attrs = rtnl_msg_ext::attrs(&msg);
-    let link_layer_type = rtnl_msg_ext::link_layer_type(&msg);
-
-    let mut base = LinkAttrs::new();
-    base.index = index;
-    base.raw_flags = flags;
-    base.encap_type = nl_linux::encap_type(link_layer_type);
-    base.net_ns_id = -1;
-    if flags & IFF_PROMISC != 0 {
-        base.promisc = 1;
+fn add_vlan_attrs(msg: &mut LinkMessage, mut link_info_nlas: Vec<Info>, vlan: &Vlan) {
+    let mut vec = vec![InfoVlan::Id(vlan.vlan_id)];
+    if let Some(protocol) = vlan.vlan_protocol {
+        // IFLA_VLAN_PROTOCOL is big endian but emitted in native endian
+        vec.push(InfoVlan::Protocol(u16::from(protocol).to_be()));
+    }
+
+    let mut flags = 0;
+    let mut mask = 0;
+    for (flag, value) in [
+        (VLAN_FLAG_REORDER_HDR, vlan.reorder_hdr),
+        (VLAN_FLAG_GVRP, vlan.gvrp),
+        (VLAN_FLAG_MVRP, vlan.mvrp),
+        (VLAN_FLAG_LOOSE_BINDING, vlan.loose_binding),
+        (VLAN_FLAG_BRIDGE_BINDING, vlan.bridge_binding),
+    ] {
+        if let Some(v) = value {
+            mask |= flag;
+            if v {
+                flags |= flag;
+            }
+        }
     }
-    if flags & IFF_ALLMULTI != 0 {
-        base.allmulti = 1;
+    if mask > 0 {
+        vec.push(InfoVlan::Flags((flags, mask)));
     }
-    if flags & IFF_MULTICAST != 0 {
-        base.multi = 1;
+
+    if !vlan.ingress_qos_map.is_empty() {
+        vec.push(InfoVlan::IngressQos(vlan_qos_mappings(&vlan.ingress_qos_map)));
+    }
+    if !vlan.egress_qos_map.is_empty() {
+        vec.push(InfoVlan::EgressQos(vlan_qos_mappings(&vlan.egress_qos_map)));
     }
+    link_info_nlas.push(Info::Data(InfoData::Vlan(vec)));
+    msg.nlas.push(Nla::Info(link_info_nlas));
+}
 
-    let mut link_kind: Option<LinkKind> = None;
-    for attr in attrs {
-        match attr {
-            Nla::IfName(name) => {
-                base.name = name.clone();
+fn vlan_qos_mappings(map: &BTreeMap<u32, u32>) -> Vec<VlanQosMapping> {
+    map.iter().map(|(from, to)| VlanQosMapping::Mapping { from: *from, to: *to }).collect()
+}
+
+fn vlan_deserialize(nlas: &[InfoVlan]) -> Vlan {
+    let mut vlan = Vlan::default();
+    for nla in nlas {
+        match nla {
+            InfoVlan::Id(id) => {
+                vlan.vlan_id = *id;
             }
-            Nla::Address(addr) => {
-                let hardware_addr = if addr.len() == 6 {
-                    MacAddr6::new(addr[0], addr[1], addr[2], addr[3], addr[4], addr[5])
-                } else {
-                    MacAddr6::default()
-                };
-                base.hardware_addr = Some(hardware_addr);
+            InfoVlan::Protocol(protocol) => {
+                vlan.vlan_protocol = VlanProtocol::try_from(*protocol).ok();
             }
-            Nla::Mtu(mtu) => {
-                base.mtu = *mtu;
+            InfoVlan::Flags((flags, mask)) => {
+                let flag = |flag: u32| (mask & flag != 0).then_some(flags & flag != 0);
+                vlan.reorder_hdr = flag(VLAN_FLAG_REORDER_HDR);
+                vlan.gvrp = flag(VLAN_FLAG_GVRP);
+                vlan.mvrp = flag(VLAN_FLAG_MVRP);
+                vlan.loose_binding = flag(VLAN_FLAG_LOOSE_BINDING);
+                vlan.bridge_binding = flag(VLAN_FLAG_BRIDGE_BINDING);
             }
-            Nla::Link(link) => {
-                base.parent_index = *link;
+            InfoVlan::IngressQos(mappings) => {
+                vlan.ingress_qos_map = vlan_qos_map(mappings);
             }
-            Nla::Master(master) => {
-                base.master_index = *master;
+            InfoVlan::EgressQos(mappings) => {
+                vlan.egress_qos_map = vlan_qos_map(mappings);
             }
+            _ => {}
+        }
+    }
+    vlan
+}
 
-            Nla::TxQueueLen(len) => {
-                base.tx_q_len = *len;
-            }
-            Nla::IfAlias(alias) => {
-                base.alias = alias.clone();
-            }
-            Nla::Stats(_stats) => {}
-            Nla::Stats64(stats) => {
-                let buffer = Stats64Buffer::new(stats);
-                base.statistics = Some(Stats64::parse(&buffer).unwrap());
-            }
-            Nla::Xdp(xdp_data) => {
-                // todo
-                debug!("xdp_data: {:?}", xdp_data);
+fn vlan_qos_map(mappings: &[VlanQosMapping]) -> BTreeMap<u32, u32> {
+    mappings.iter()
+        .filter_map(|it| match it {
+            VlanQosMapping::Mapping { from, to } => Some((*from, *to)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn macvlan_nlas(macvlan: &Macvlan) -> Vec<InfoMacVlan> {
+    let mut vec = Vec::new();
+    if let Some(mode) = macvlan.mode {
+        vec.push(InfoMacVlan::Mode(mode.into()));
+    }
+    if macvlan.nopromisc {
+        vec.push(InfoMacVlan::Flags(MACVLAN_FLAG_NOPROMISC));
+    }
+    if !macvlan.mac_addrs.is_empty() {
+        vec.push(InfoMacVlan::MacAddrMode(MACVLAN_MACADDR_SET));
+        vec.push(macvlan_mac_addr_data(&macvlan.mac_addrs));
+    }
+    if let Some(len) = macvlan.bc_queue_len {
+        vec.push(InfoMacVlan::BcQueueLen(len));
+    }
+    vec
+}
+
+fn macvlan_mac_addr_data(addrs: &[MacAddr6]) -> InfoMacVlan {
+    InfoMacVlan::MacAddrData(addrs.iter().map(|it| InfoMacVlan::MacAddr(it.into_array())).collect())
+}
+
+// macvlan and macvtap share the IFLA_MACVLAN_* attributes, but have distinct nla types
+fn convert_macvlan_nlas<F, T>(nlas: &[F]) -> Vec<T>
+    where F: netlink_packet_utils::nla::Nla,
+          T: for<'a> Parseable<NlaBuffer<&'a [u8]>> {
+    utils::parse_nlas(&utils::emit_nlas(nlas))
+}
+
+fn macvlan_deserialize(nlas: &[InfoMacVlan]) -> Macvlan {
+    let mut macvlan = Macvlan::default();
+    for nla in nlas {
+        match nla {
+            InfoMacVlan::Mode(mode) => {
+                macvlan.mode = MacvlanMode::try_from(*mode).ok();
             }
-            Nla::OperState(state) => {
-                base.oper_state = *state;
+            InfoMacVlan::Flags(flags) => {
+                macvlan.nopromisc = flags & MACVLAN_FLAG_NOPROMISC != 0;
             }
-            Nla::PhysSwitchId(vec) => {
-                if vec.len() == 4 {
-                    base.phys_switch_id = u32::from_ne_bytes([vec[0], vec[1], vec[2], vec[3]]);
+            InfoMacVlan::MacAddrData(addrs) => {
+                for addr in addrs {
+                    if let InfoMacVlan::MacAddr(addr) = addr {
+                        macvlan.mac_addrs.push(MacAddr6::from(*addr));
+                    }
                 }
             }
-            Nla::NetnsId(id) => {
-                base.net_ns_id = *id;
-            }
-            Nla::GsoMaxSegs(segs) => {
-                base.gso_max_segs = *segs;
-            }
-            Nla::GsoMaxSize(size) => {
-                base.gso_max_size = *size;
-            }
-            Nla::NumTxQueues(queues) => {
-                base.num_tx_queues = *queues;
+            InfoMacVlan::BcQueueLen(len) => {
+                macvlan.bc_queue_len = Some(*len);
             }
-            Nla::NumRxQueues(queues) => {
-                base.num_rx_queues = *queues;
+            InfoMacVlan::BcQueueLenUsed(len) => {
+                macvlan.bc_queue_len_used = *len;
             }
-            Nla::Group(group) => {
-                base.group = *group;
+            _ => {}
+        }
+    }
+    macvlan
+}
+
+fn ipvlan_nlas(ipvlan: &Ipvlan) -> Vec<InfoIpVlan> {
+    let mut nlas = vec![];
+    if let Some(mode) = ipvlan.mode {
+        nlas.push(InfoIpVlan::Mode(mode.into()));
+    }
+    if let Some(flag) = ipvlan.flag {
+        nlas.push(InfoIpVlan::Flags(flag.into()));
+    }
+    nlas
+}
+
+fn ipvlan_deserialize(nlas: &[InfoIpVlan]) -> Ipvlan {
+    let mut ipvlan = Ipvlan::default();
+    for nla in nlas {
+        match nla {
+            InfoIpVlan::Mode(mode) => {
+                ipvlan.mode = IpvlanMode::try_from(*mode).ok();
             }
-            Nla::VfInfoList(_data) => {
-                // base.vfs = data.to_vec();
+            InfoIpVlan::Flags(flags) => {
+                ipvlan.flag = IpvlanFlag::try_from(*flags).ok();
             }
-            Nla::Info(infos) => {
-                for info in infos {
-                    // debug!("info: {:?}", info);
-                    match info {
-                        Info::Kind(kind) => match kind {
-                            InfoKind::Bridge => {
-                                link_kind = Some(LinkKind::Bridge(Bridge::default()));
-                            }
-                            InfoKind::Veth => {
-                                link_kind = Some(LinkKind::Veth(Veth::default()));
-                            }
-                            InfoKind::IpTun => {
-                                link_kind = Some(LinkKind::Tuntap(Tuntap::default()));
-                            }
+            _ => {}
+        }
+    }
+    ipvlan
+}
+
+fn bond_nlas(bond: &Bond) -> Vec<InfoBond> {
+    let mut vec = Vec::new();
+    if let Some(mode) = bond.mode {
+        vec.push(InfoBond::Mode(mode.into()));
+    }
+    if let Some(v) = bond.active_slave {
+        vec.push(InfoBond::ActivePort(v));
+    }
+    if let Some(v) = bond.miimon {
+        vec.push(InfoBond::MiiMon(v));
+    }
+    if let Some(v) = bond.up_delay {
+        vec.push(InfoBond::UpDelay(v));
+    }
+    if let Some(v) = bond.down_delay {
+        vec.push(InfoBond::DownDelay(v));
+    }
+    if let Some(v) = bond.peer_notify_delay {
+        vec.push(InfoBond::PeerNotifDelay(v));
+    }
+    if let Some(v) = bond.use_carrier {
+        vec.push(InfoBond::UseCarrier(v as u8));
+    }
+    if let Some(v) = bond.arp_interval {
+        vec.push(InfoBond::ArpInterval(v));
+    }
+    if !bond.arp_ip_targets.is_empty() {
+        vec.push(InfoBond::ArpIpTarget(bond.arp_ip_targets.clone()));
+    }
+    if !bond.ns_ip6_targets.is_empty() {
+        vec.push(InfoBond::NsIp6Target(bond.ns_ip6_targets.clone()));
+    }
+    if let Some(v) = bond.arp_validate {
+        vec.push(InfoBond::ArpValidate(v));
+    }
+    if let Some(v) = bond.arp_all_targets {
+        vec.push(InfoBond::ArpAllTargets(v));
+    }
+    if let Some(v) = bond.primary {
+        vec.push(InfoBond::Primary(v));
+    }
+    if let Some(v) = bond.primary_reselect {
+        vec.push(InfoBond::PrimaryReselect(v));
+    }
+    if let Some(v) = bond.fail_over_mac {
+        vec.push(InfoBond::FailOverMac(v));
+    }
+    if let Some(v) = bond.xmit_hash_policy {
+        vec.push(InfoBond::XmitHashPolicy(v.into()));
+    }
+    if let Some(v) = bond.resend_igmp {
+        vec.push(InfoBond::ResendIgmp(v));
+    }
+    if let Some(v) = bond.num_peer_notif {
+        vec.push(InfoBond::NumPeerNotif(v));
+    }
+    if let Some(v) = bond.all_slaves_active {
+        vec.push(InfoBond::AllPortsActive(v as u8));
+    }
+    if let Some(v) = bond.min_links {
+        vec.push(InfoBond::MinLinks(v));
+    }
+    if let Some(v) = bond.lp_interval {
+        vec.push(InfoBond::LpInterval(v));
+    }
+    if let Some(v) = bond.packets_per_slave {
+        vec.push(InfoBond::PacketsPerPort(v));
+    }
+    if let Some(v) = bond.lacp_active {
+        vec.push(InfoBond::AdLacpActive(v as u8));
+    }
+    if let Some(v) = bond.lacp_rate {
+        vec.push(InfoBond::AdLacpRate(v.into()));
+    }
+    if let Some(v) = bond.ad_select {
+        vec.push(InfoBond::AdSelect(v.into()));
+    }
+    if let Some(v) = bond.ad_actor_sys_prio {
+        vec.push(InfoBond::AdActorSysPrio(v));
+    }
+    if let Some(v) = bond.ad_user_port_key {
+        vec.push(InfoBond::AdUserPortKey(v));
+    }
+    if let Some(v) = bond.ad_actor_system {
+        vec.push(InfoBond::AdActorSystem(v.into_array()));
+    }
+    if let Some(v) = bond.tlb_dynamic_lb {
+        vec.push(InfoBond::TlbDynamicLb(v as u8));
+    }
+    if let Some(v) = bond.missed_max {
+        vec.push(InfoBond::MissedMax(v));
+    }
+    vec
+}
+
+fn bond_deserialize(nlas: &[InfoBond]) -> Bond {
+    let mut bond = Bond::default();
+    for nla in nlas {
+        match nla {
+            InfoBond::Mode(v) => bond.mode = BondMode::try_from(*v).ok(),
+            InfoBond::ActivePort(v) => bond.active_slave = Some(*v),
+            InfoBond::MiiMon(v) => bond.miimon = Some(*v),
+            InfoBond::UpDelay(v) => bond.up_delay = Some(*v),
+            InfoBond::DownDelay(v) => bond.down_delay = Some(*v),
+            InfoBond::PeerNotifDelay(v) => bond.peer_notify_delay = Some(*v),
+            InfoBond::UseCarrier(v) => bond.use_carrier = Some(*v != 0),
+            InfoBond::ArpInterval(v) => bond.arp_interval = Some(*v),
+            InfoBond::ArpIpTarget(v) => bond.arp_ip_targets = v.clone(),
+            InfoBond::NsIp6Target(v) => bond.ns_ip6_targets = v.clone(),
+            InfoBond::ArpValidate(v) => bond.arp_validate = Some(*v),
+            InfoBond::ArpAllTargets(v) => bond.arp_all_targets = Some(*v),
+            InfoBond::Primary(v) => bond.primary = Some(*v),
+            InfoBond::PrimaryReselect(v) => bond.primary_reselect = Some(*v),
+            InfoBond::FailOverMac(v) => bond.fail_over_mac = Some(*v),
+            InfoBond::XmitHashPolicy(v) => bond.xmit_hash_policy = BondXmitHashPolicy::try_from(*v).ok(),
+            InfoBond::ResendIgmp(v) => bond.resend_igmp = Some(*v),
+            InfoBond::NumPeerNotif(v) => bond.num_peer_notif = Some(*v),
+            InfoBond::AllPortsActive(v) => bond.all_slaves_active = Some(*v != 0),
+            InfoBond::MinLinks(v) => bond.min_links = Some(*v),
+            InfoBond::LpInterval(v) => bond.lp_interval = Some(*v),
+            InfoBond::PacketsPerPort(v) => bond.packets_per_slave = Some(*v),
+            InfoBond::AdLacpActive(v) => bond.lacp_active = Some(*v != 0),
+            InfoBond::AdLacpRate(v) => bond.lacp_rate = BondLacpRate::try_from(*v).ok(),
+            InfoBond::AdSelect(v) => bond.ad_select = BondAdSelect::try_from(*v).ok(),
+            InfoBond::AdActorSysPrio(v) => bond.ad_actor_sys_prio = Some(*v),
+            InfoBond::AdUserPortKey(v) => bond.ad_user_port_key = Some(*v),
+            InfoBond::AdActorSystem(v) => bond.ad_actor_system = Some(MacAddr6::from(*v)),
+            InfoBond::TlbDynamicLb(v) => bond.tlb_dynamic_lb = Some(*v != 0),
+            InfoBond::MissedMax(v) => bond.missed_max = Some(*v),
+            InfoBond::AdInfo(infos) => {
+                let mut ad_info = BondAdInfo::default();
+                for info in infos {
+                    match info {
+                        InfoBondAdInfo::Aggregator(v) => ad_info.aggregator_id = *v,
+                        InfoBondAdInfo::NumPorts(v) => ad_info.num_ports = *v,
+                        InfoBondAdInfo::ActorKey(v) => ad_info.actor_key = *v,
+                        InfoBondAdInfo::PartnerKey(v) => ad_info.partner_key = *v,
+                        InfoBondAdInfo::PartnerMac(v) => ad_info.partner_mac = MacAddr6::from(*v),
+                        _ => {}
+                    }
+                }
+                bond.ad_info = Some(ad_info);
+            }
+            _ => {}
+        }
+    }
+    bond
+}
+
+fn bridge_slave_nlas(slave: &BridgeSlave) -> Vec<DefaultNla> {
+    let mut nlas = Vec::new();
+    if let Some(state) = slave.state {
+        nlas.push(nla_u8(IFLA_BRPORT_STATE, state.into()));
+    }
+    if let Some(priority) = slave.priority {
+        nlas.push(nla_u16(IFLA_BRPORT_PRIORITY, priority));
+    }
+    if let Some(cost) = slave.cost {
+        nlas.push(nla_u32(IFLA_BRPORT_COST, cost));
+    }
+    let flags = [
+        (IFLA_BRPORT_MODE, slave.hairpin),
+        (IFLA_BRPORT_GUARD, slave.guard),
+        (IFLA_BRPORT_PROTECT, slave.root_block),
+        (IFLA_BRPORT_FAST_LEAVE, slave.fast_leave),
+        (IFLA_BRPORT_LEARNING, slave.learning),
+        (IFLA_BRPORT_UNICAST_FLOOD, slave.unicast_flood),
+        (IFLA_BRPORT_MCAST_FLOOD, slave.multicast_flood),
+        (IFLA_BRPORT_BCAST_FLOOD, slave.broadcast_flood),
+        (IFLA_BRPORT_PROXYARP, slave.proxy_arp),
+        (IFLA_BRPORT_PROXYARP_WIFI, slave.proxy_arp_wifi),
+        (IFLA_BRPORT_NEIGH_SUPPRESS, slave.neigh_suppress),
+        (IFLA_BRPORT_ISOLATED, slave.isolated),
+        (IFLA_BRPORT_LOCKED, slave.locked),
+        (IFLA_BRPORT_MAB, slave.mab),
+        (IFLA_BRPORT_VLAN_TUNNEL, slave.vlan_tunnel),
+    ];
+    for (kind, value) in flags {
+        if let Some(value) = value {
+            nlas.push(nla_u8(kind, value as u8));
+        }
+    }
+    nlas
+}
+
+fn bridge_slave_deserialize(nlas: &[DefaultNla]) -> BridgeSlave {
+    let mut slave = BridgeSlave::default();
+    for nla in nlas {
+        let flag = utils::default_nla_u8(nla).map(|v| v != 0);
+        match nla.kind() {
+            IFLA_BRPORT_STATE => {
+                slave.state = utils::default_nla_u8(nla).and_then(|v| BridgePortState::try_from(v).ok());
+            }
+            IFLA_BRPORT_PRIORITY => slave.priority = utils::default_nla_u16(nla),
+            IFLA_BRPORT_COST => slave.cost = utils::default_nla_u32(nla),
+            IFLA_BRPORT_MODE => slave.hairpin = flag,
+            IFLA_BRPORT_GUARD => slave.guard = flag,
+            IFLA_BRPORT_PROTECT => slave.root_block = flag,
+            IFLA_BRPORT_FAST_LEAVE => slave.fast_leave = flag,
+            IFLA_BRPORT_LEARNING => slave.learning = flag,
+            IFLA_BRPORT_UNICAST_FLOOD => slave.unicast_flood = flag,
+            IFLA_BRPORT_MCAST_FLOOD => slave.multicast_flood = flag,
+            IFLA_BRPORT_BCAST_FLOOD => slave.broadcast_flood = flag,
+            IFLA_BRPORT_PROXYARP => slave.proxy_arp = flag,
+            IFLA_BRPORT_PROXYARP_WIFI => slave.proxy_arp_wifi = flag,
+            IFLA_BRPORT_NEIGH_SUPPRESS => slave.neigh_suppress = flag,
+            IFLA_BRPORT_ISOLATED => slave.isolated = flag,
+            IFLA_BRPORT_LOCKED => slave.locked = flag,
+            IFLA_BRPORT_MAB => slave.mab = flag,
+            IFLA_BRPORT_VLAN_TUNNEL => slave.vlan_tunnel = flag,
+            _ => {}
+        }
+    }
+    slave
+}
+
+fn bond_slave_deserialize(nlas: &[InfoBondPort]) -> BondSlave {
+    let mut slave = BondSlave::default();
+    for nla in nlas {
+        match nla {
+            InfoBondPort::BondPortState(v) => slave.state = Some(*v),
+            InfoBondPort::MiiStatus(v) => slave.mii_status = Some(*v),
+            InfoBondPort::LinkFailureCount(v) => slave.link_failure_count = *v,
+            InfoBondPort::PermHwaddr(v) if v.len() == 6 => {
+                slave.perm_hardware_addr = Some(MacAddr6::new(v[0], v[1], v[2], v[3], v[4], v[5]));
+            }
+            InfoBondPort::QueueId(v) => slave.queue_id = *v,
+            InfoBondPort::Prio(v) => slave.prio = *v,
+            InfoBondPort::Other(nla) => {
+                let value = utils::default_nla_value(nla);
+                match nla.kind() {
+                    IFLA_BOND_PORT_AD_AGGREGATOR_ID if value.len() == 2 => {
+                        slave.aggregator_id = Some(u16::from_ne_bytes([value[0], value[1]]));
+                    }
+                    IFLA_BOND_PORT_AD_ACTOR_OPER_PORT_STATE if value.len() == 1 => {
+                        slave.ad_actor_oper_port_state = Some(value[0]);
+                    }
+                    IFLA_BOND_PORT_AD_PARTNER_OPER_PORT_STATE if value.len() == 2 => {
+                        slave.ad_partner_oper_port_state = Some(u16::from_ne_bytes([value[0], value[1]]));
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+    slave
+}
+
+fn nla_ip(kind: u16, ip: &IpAddr) -> DefaultNla {
+    DefaultNla::new(kind, utils::ip_to_bytes(ip))
+}
+
+fn nla_u8(kind: u16, value: u8) -> DefaultNla {
+    DefaultNla::new(kind, vec![value])
+}
+
+fn nla_u16(kind: u16, value: u16) -> DefaultNla {
+    DefaultNla::new(kind, value.to_ne_bytes().to_vec())
+}
+
+fn nla_u32(kind: u16, value: u32) -> DefaultNla {
+    DefaultNla::new(kind, value.to_ne_bytes().to_vec())
+}
+
+// the ipv6 flowinfo of a tunnel holds the traffic class and the flow label
+fn tunnel_flowinfo(tclass: u8, flow_label: u32) -> u32 {
+    ((tclass as u32) << 20) | (flow_label & 0xfffff)
+}
+
+// encap attributes are laid out the same for gre and ip tunnels, starting at `type_kind`:
+// type, flags, sport, dport
+fn tunnel_encap_nlas(encap: &TunnelEncap, type_kind: u16) -> Vec<DefaultNla> {
+    vec![
+        nla_u16(type_kind, encap.encap_type.into()),
+        nla_u16(type_kind + 1, encap.flags),
+        nla_u16(type_kind + 2, encap.sport.to_be()),
+        nla_u16(type_kind + 3, encap.dport.to_be()),
+    ]
+}
+
+fn tunnel_encap_deserialize(encap: &mut TunnelEncap, nla: &DefaultNla, type_kind: u16) {
+    let Some(value) = utils::default_nla_u16(nla) else {
+        return;
+    };
+    match nla.kind() - type_kind {
+        0 => encap.encap_type = TunnelEncapType::from(value),
+        1 => encap.flags = value,
+        2 => encap.sport = u16::from_be(value),
+        3 => encap.dport = u16::from_be(value),
+        _ => {}
+    }
+}
+
+fn gre_nlas(gre: &Gre, ip6: bool) -> Vec<DefaultNla> {
+    let mut vec = Vec::new();
+    if gre.link > 0 {
+        vec.push(nla_u32(IFLA_GRE_LINK, gre.link));
+    }
+    if let Some(local) = &gre.local {
+        vec.push(nla_ip(IFLA_GRE_LOCAL, local));
+    }
+    if let Some(remote) = &gre.remote {
+        vec.push(nla_ip(IFLA_GRE_REMOTE, remote));
+    }
+    if gre.ttl > 0 {
+        vec.push(nla_u8(IFLA_GRE_TTL, gre.ttl));
+    }
+    if ip6 {
+        if gre.tos > 0 || gre.flow_label > 0 {
+            vec.push(nla_u32(IFLA_GRE_FLOWINFO, tunnel_flowinfo(gre.tos, gre.flow_label).to_be()));
+        }
+        if let Some(limit) = gre.encap_limit {
+            vec.push(nla_u8(IFLA_GRE_ENCAP_LIMIT, limit));
+        }
+        if gre.flags > 0 {
+            vec.push(nla_u32(IFLA_GRE_FLAGS, gre.flags));
+        }
+    } else if gre.tos > 0 {
+        vec.push(nla_u8(IFLA_GRE_TOS, gre.tos));
+    }
+
+    let mut iflags = gre.iflags;
+    let mut oflags = gre.oflags;
+    if gre.ikey > 0 {
+        iflags |= GRE_KEY;
+        vec.push(nla_u32(IFLA_GRE_IKEY, gre.ikey.to_be()));
+    }
+    if gre.okey > 0 {
+        oflags |= GRE_KEY;
+        vec.push(nla_u32(IFLA_GRE_OKEY, gre.okey.to_be()));
+    }
+    if iflags > 0 {
+        vec.push(nla_u16(IFLA_GRE_IFLAGS, iflags.to_be()));
+    }
+    if oflags > 0 {
+        vec.push(nla_u16(IFLA_GRE_OFLAGS, oflags.to_be()));
+    }
+
+    if let Some(v) = gre.pmtudisc {
+        vec.push(nla_u8(IFLA_GRE_PMTUDISC, v as u8));
+    }
+    if let Some(v) = gre.ignore_df {
+        vec.push(nla_u8(IFLA_GRE_IGNORE_DF, v as u8));
+    }
+    if gre.fwmark > 0 {
+        vec.push(nla_u32(IFLA_GRE_FWMARK, gre.fwmark));
+    }
+    if let Some(encap) = &gre.encap {
+        vec.extend(tunnel_encap_nlas(encap, IFLA_GRE_ENCAP_TYPE));
+    }
+    if gre.collect_metadata {
+        vec.push(DefaultNla::new(IFLA_GRE_COLLECT_METADATA, vec![]));
+    }
+    if let Some(v) = gre.erspan_ver {
+        vec.push(nla_u8(IFLA_GRE_ERSPAN_VER, v));
+    }
+    if let Some(v) = gre.erspan_index {
+        vec.push(nla_u32(IFLA_GRE_ERSPAN_INDEX, v));
+    }
+    if let Some(v) = gre.erspan_dir {
+        vec.push(nla_u8(IFLA_GRE_ERSPAN_DIR, v));
+    }
+    if let Some(v) = gre.erspan_hwid {
+        vec.push(nla_u16(IFLA_GRE_ERSPAN_HWID, v));
+    }
+    vec
+}
+
+fn gre_deserialize(nlas: &[DefaultNla]) -> Gre {
+    let mut gre = Gre::default();
+    let mut encap = TunnelEncap::default();
+    for nla in nlas {
+        let value = utils::default_nla_value(nla);
+        match nla.kind() {
+            IFLA_GRE_LINK => gre.link = utils::default_nla_u32(nla).unwrap_or_default(),
+            IFLA_GRE_LOCAL => gre.local = utils::bytes_to_ip_by_len(&value).ok().filter(|ip| !ip.is_unspecified()),
+            IFLA_GRE_REMOTE => gre.remote = utils::bytes_to_ip_by_len(&value).ok().filter(|ip| !ip.is_unspecified()),
+            IFLA_GRE_TTL => gre.ttl = utils::default_nla_u8(nla).unwrap_or_default(),
+            IFLA_GRE_TOS => gre.tos = utils::default_nla_u8(nla).unwrap_or_default(),
+            IFLA_GRE_FLOWINFO => {
+                let flowinfo = u32::from_be(utils::default_nla_u32(nla).unwrap_or_default());
+                gre.tos = (flowinfo >> 20) as u8;
+                gre.flow_label = flowinfo & 0xfffff;
+            }
+            IFLA_GRE_ENCAP_LIMIT => gre.encap_limit = utils::default_nla_u8(nla),
+            IFLA_GRE_FLAGS => gre.flags = utils::default_nla_u32(nla).unwrap_or_default(),
+            IFLA_GRE_IFLAGS => gre.iflags = u16::from_be(utils::default_nla_u16(nla).unwrap_or_default()),
+            IFLA_GRE_OFLAGS => gre.oflags = u16::from_be(utils::default_nla_u16(nla).unwrap_or_default()),
+            IFLA_GRE_IKEY => gre.ikey = u32::from_be(utils::default_nla_u32(nla).unwrap_or_default()),
+            IFLA_GRE_OKEY => gre.okey = u32::from_be(utils::default_nla_u32(nla).unwrap_or_default()),
+            IFLA_GRE_PMTUDISC => gre.pmtudisc = utils::default_nla_u8(nla).map(|v| v != 0),
+            IFLA_GRE_IGNORE_DF => gre.ignore_df = utils::default_nla_u8(nla).map(|v| v != 0),
+            IFLA_GRE_FWMARK => gre.fwmark = utils::default_nla_u32(nla).unwrap_or_default(),
+            IFLA_GRE_ENCAP_TYPE..=IFLA_GRE_ENCAP_DPORT => tunnel_encap_deserialize(&mut encap, nla, IFLA_GRE_ENCAP_TYPE),
+            IFLA_GRE_COLLECT_METADATA => gre.collect_metadata = true,
+            IFLA_GRE_ERSPAN_VER => gre.erspan_ver = utils::default_nla_u8(nla),
+            IFLA_GRE_ERSPAN_INDEX => gre.erspan_index = utils::default_nla_u32(nla),
+            IFLA_GRE_ERSPAN_DIR => gre.erspan_dir = utils::default_nla_u8(nla),
+            IFLA_GRE_ERSPAN_HWID => gre.erspan_hwid = utils::default_nla_u16(nla),
+            _ => {}
+        }
+    }
+    if encap.encap_type != TunnelEncapType::None {
+        gre.encap = Some(encap);
+    }
+    gre
+}
+
+fn iptun_nlas(iptun: &Iptun, link_kind: &LinkKind) -> Vec<DefaultNla> {
+    let mut vec = Vec::new();
+    if iptun.link > 0 {
+        vec.push(nla_u32(IFLA_IPTUN_LINK, iptun.link));
+    }
+    if let Some(local) = &iptun.local {
+        vec.push(nla_ip(IFLA_IPTUN_LOCAL, local));
+    }
+    if let Some(remote) = &iptun.remote {
+        vec.push(nla_ip(IFLA_IPTUN_REMOTE, remote));
+    }
+    if iptun.ttl > 0 {
+        vec.push(nla_u8(IFLA_IPTUN_TTL, iptun.ttl));
+    }
+    match link_kind {
+        LinkKind::Ip6tnl(_) => {
+            if iptun.tos > 0 || iptun.flow_label > 0 {
+                vec.push(nla_u32(IFLA_IPTUN_FLOWINFO, tunnel_flowinfo(iptun.tos, iptun.flow_label).to_be()));
+            }
+            if let Some(limit) = iptun.encap_limit {
+                vec.push(nla_u8(IFLA_IPTUN_ENCAP_LIMIT, limit));
+            }
+            if iptun.flags > 0 {
+                vec.push(nla_u32(IFLA_IPTUN_FLAGS, iptun.flags));
+            }
+        }
+        _ => {
+            if iptun.tos > 0 {
+                vec.push(nla_u8(IFLA_IPTUN_TOS, iptun.tos));
+            }
+            // sit has 16 bit flags
+            if iptun.flags > 0 {
+                vec.push(nla_u16(IFLA_IPTUN_FLAGS, iptun.flags as u16));
+            }
+        }
+    }
+    if let Some(proto) = iptun.proto {
+        vec.push(nla_u8(IFLA_IPTUN_PROTO, proto));
+    }
+    if let Some(v) = iptun.pmtudisc {
+        vec.push(nla_u8(IFLA_IPTUN_PMTUDISC, v as u8));
+    }
+    if iptun.fwmark > 0 {
+        vec.push(nla_u32(IFLA_IPTUN_FWMARK, iptun.fwmark));
+    }
+    if let Some(encap) = &iptun.encap {
+        vec.extend(tunnel_encap_nlas(encap, IFLA_IPTUN_ENCAP_TYPE));
+    }
+    if iptun.collect_metadata {
+        vec.push(DefaultNla::new(IFLA_IPTUN_COLLECT_METADATA, vec![]));
+    }
+    vec
+}
+
+fn iptun_deserialize(nlas: &[DefaultNla]) -> Iptun {
+    let mut iptun = Iptun::default();
+    let mut encap = TunnelEncap::default();
+    for nla in nlas {
+        let value = utils::default_nla_value(nla);
+        match nla.kind() {
+            IFLA_IPTUN_LINK => iptun.link = utils::default_nla_u32(nla).unwrap_or_default(),
+            IFLA_IPTUN_LOCAL => iptun.local = utils::bytes_to_ip_by_len(&value).ok().filter(|ip| !ip.is_unspecified()),
+            IFLA_IPTUN_REMOTE => iptun.remote = utils::bytes_to_ip_by_len(&value).ok().filter(|ip| !ip.is_unspecified()),
+            IFLA_IPTUN_TTL => iptun.ttl = utils::default_nla_u8(nla).unwrap_or_default(),
+            IFLA_IPTUN_TOS => iptun.tos = utils::default_nla_u8(nla).unwrap_or_default(),
+            IFLA_IPTUN_FLOWINFO => {
+                let flowinfo = u32::from_be(utils::default_nla_u32(nla).unwrap_or_default());
+                iptun.tos = (flowinfo >> 20) as u8;
+                iptun.flow_label = flowinfo & 0xfffff;
+            }
+            IFLA_IPTUN_ENCAP_LIMIT => iptun.encap_limit = utils::default_nla_u8(nla),
+            IFLA_IPTUN_FLAGS => {
+                iptun.flags = utils::default_nla_u32(nla)
+                    .or_else(|| utils::default_nla_u16(nla).map(u32::from))
+                    .unwrap_or_default();
+            }
+            IFLA_IPTUN_PROTO => iptun.proto = utils::default_nla_u8(nla),
+            IFLA_IPTUN_PMTUDISC => iptun.pmtudisc = utils::default_nla_u8(nla).map(|v| v != 0),
+            IFLA_IPTUN_FWMARK => iptun.fwmark = utils::default_nla_u32(nla).unwrap_or_default(),
+            IFLA_IPTUN_ENCAP_TYPE..=IFLA_IPTUN_ENCAP_DPORT => tunnel_encap_deserialize(&mut encap, nla, IFLA_IPTUN_ENCAP_TYPE),
+            IFLA_IPTUN_COLLECT_METADATA => iptun.collect_metadata = true,
+            _ => {}
+        }
+    }
+    if encap.encap_type != TunnelEncapType::None {
+        iptun.encap = Some(encap);
+    }
+    iptun
+}
+
+// raw_info_data_deserialize decodes the IFLA_INFO_DATA of kinds the library keeps as raw bytes
+fn raw_info_data_deserialize(link_kind: &mut LinkKind, bytes: &[u8]) {
+    match link_kind {
+        LinkKind::Ipvtap(ipvtap) => *ipvtap = ipvlan_deserialize(&utils::parse_nlas(bytes)),
+        LinkKind::Vxlan(vxlan) => *vxlan = vxlan_deserialize(&utils::parse_nlas(bytes)),
+        LinkKind::Geneve(geneve) => *geneve = geneve_deserialize(&utils::parse_nlas(bytes)),
+        LinkKind::Gre(gre)
+        | LinkKind::Gretap(gre)
+        | LinkKind::Ip6gre(gre)
+        | LinkKind::Ip6gretap(gre)
+        | LinkKind::Erspan(gre) => *gre = gre_deserialize(&utils::parse_nlas(bytes)),
+        LinkKind::Ipip(iptun)
+        | LinkKind::Sit(iptun)
+        | LinkKind::Ip6tnl(iptun) => *iptun = iptun_deserialize(&utils::parse_nlas(bytes)),
+        _ => {}
+    }
+}
+
+// macvlan_mac_addr_add adds a source mac address to a macvlan or macvtap in source mode.
+// Equivalent to: `ip link set $link type macvlan macaddr add $addr`
+pub fn macvlan_mac_addr_add(link: &Link, addr: MacAddr6) -> anyhow::Result<()> {
+    macvlan_mac_addr_change(link, MACVLAN_MACADDR_ADD, vec![InfoMacVlan::MacAddr(addr.into_array())])
+}
+
+// macvlan_mac_addr_del deletes a source mac address of a macvlan or macvtap in source mode.
+// Equivalent to: `ip link set $link type macvlan macaddr del $addr`
+pub fn macvlan_mac_addr_del(link: &Link, addr: MacAddr6) -> anyhow::Result<()> {
+    macvlan_mac_addr_change(link, MACVLAN_MACADDR_DEL, vec![InfoMacVlan::MacAddr(addr.into_array())])
+}
+
+// macvlan_mac_addr_flush deletes all the source mac addresses of a macvlan or macvtap.
+// Equivalent to: `ip link set $link type macvlan macaddr flush`
+pub fn macvlan_mac_addr_flush(link: &Link) -> anyhow::Result<()> {
+    macvlan_mac_addr_change(link, MACVLAN_MACADDR_FLUSH, vec![])
+}
+
+// macvlan_mac_addr_set replaces the source mac addresses of a macvlan or macvtap.
+// Equivalent to: `ip link set $link type macvlan macaddr set $addr...`
+pub fn macvlan_mac_addr_set(link: &Link, addrs: &[MacAddr6]) -> anyhow::Result<()> {
+    macvlan_mac_addr_change(link, MACVLAN_MACADDR_SET, vec![macvlan_mac_addr_data(addrs)])
+}
+
+fn macvlan_mac_addr_change(link: &Link, mode: u32, addrs: Vec<InfoMacVlan>) -> anyhow::Result<()> {
+    let mut nlas = vec![InfoMacVlan::MacAddrMode(mode)];
+    nlas.extend(addrs);
+    let data = match link.link_kind {
+        LinkKind::Macvlan(_) => InfoData::MacVlan(nlas),
+        LinkKind::Macvtap(_) => InfoData::MacVtap(convert_macvlan_nlas(&nlas)),
+        _ => bail!("link {} is not a macvlan or macvtap", link.attrs().name),
+    };
+
+    let mut msg = LinkMessage::default();
+    msg.header.index = link.attrs().index;
+    msg.nlas.push(Nla::Info(vec![Info::Kind(get_link_kind(&link.link_kind)), Info::Data(data)]));
+    NetlinkHandle::new().execute(RtnlMessage::NewLink(msg), NLM_F_ACK)?;
+    Ok(())
+}
+
+// LinkSetMaster sets the master of the link device.
+// Equivalent to: `ip link set $link master $master`
+pub fn link_set_master(link: &Link, master: &Link) -> anyhow::Result<()> {
+    let master_index = master.link_attrs.index;
+    link_set_master_by_index(link, master_index)
+}
+
+// link_vrf returns the vrf `link` is enslaved to, None if its master is not a vrf.
+// Equivalent to: `ip link show vrf $vrf dev $link`
+pub fn link_vrf(link: &Link) -> anyhow::Result<Option<Link>> {
+    if link.link_attrs.master_index == 0 {
+        return Ok(None);
+    }
+    let master = link_by_index(link.link_attrs.master_index)?;
+    Ok(master.filter(|master| matches!(master.link_kind, LinkKind::Vrf(_))))
+}
+
+// link_set_bridge_slave sets the bridge port attributes of `link`, fields left as None
+// are not changed. `link` must be enslaved to a bridge.
+// Equivalent to: `bridge link set dev $link learning on hairpin off ...`
+pub fn link_set_bridge_slave(link: &Link, slave: &BridgeSlave) -> anyhow::Result<()> {
+    let mut msg = LinkMessage::default();
+    msg.header.interface_family = FAMILY_BRIDGE;
+    msg.header.index = link.attrs().index;
+    let protinfo = utils::emit_nlas(&bridge_slave_nlas(slave));
+    msg.nlas.push(Nla::Other(DefaultNla::new(IFLA_PROTINFO | NLA_F_NESTED, protinfo)));
+    NetlinkHandle::new().execute(RtnlMessage::SetLink(msg), NLM_F_ACK)?;
+    Ok(())
+}
+
+pub(crate) fn link_set_master_by_index(link: &Link, master_index: u32) -> anyhow::Result<()> {
+    let mut msg = LinkMessage::default();
+    msg.header.index = link.attrs().index;
+    msg.nlas.push(Nla::Master(master_index));
+    let _ = NetlinkHandle::new().execute(RtnlMessage::SetLink(msg), NLM_F_ACK)?;
+    Ok(())
+}
+
+
+fn get_link_kind(link: &LinkKind) -> InfoKind {
+    match link {
+        LinkKind::Veth { .. } => InfoKind::Veth,
+        LinkKind::Bridge { .. } => InfoKind::Bridge,
+        LinkKind::Device { .. } => InfoKind::Dummy,
+        LinkKind::Tuntap { .. } => InfoKind::Tun,
+        LinkKind::Dummy { .. } => { InfoKind::Dummy }
+        LinkKind::Vxlan(_) => { InfoKind::Vxlan }
+        LinkKind::Vlan(_) => InfoKind::Vlan,
+        LinkKind::Macvlan(_) => InfoKind::MacVlan,
+        LinkKind::Macvtap(_) => InfoKind::MacVtap,
+        LinkKind::Ipvlan(_) => InfoKind::IpVlan,
+        LinkKind::Ipvtap(_) => InfoKind::Other("ipvtap".to_string()),
+        LinkKind::Bond(_) => InfoKind::Bond,
+        LinkKind::Gre(_) => InfoKind::GreTun,
+        LinkKind::Gretap(_) => InfoKind::GreTap,
+        LinkKind::Ip6gre(_) => InfoKind::GreTun6,
+        LinkKind::Ip6gretap(_) => InfoKind::GreTap6,
+        LinkKind::Erspan(_) => InfoKind::Other("erspan".to_string()),
+        LinkKind::Ipip(_) => InfoKind::IpTun,
+        LinkKind::Sit(_) => InfoKind::SitTun,
+        LinkKind::Ip6tnl(_) => InfoKind::Other("ip6tnl".to_string()),
+        LinkKind::Geneve(_) => InfoKind::Other("geneve".to_string()),
+        LinkKind::Vrf(_) => InfoKind::Vrf,
+        LinkKind::Wireguard(_) => InfoKind::Wireguard,
+    }
+}
+
+
+fn link_deserialize(msg: &RtnlMessage) -> anyhow::Result<Link> {
+    let index = rtnl_msg_ext::index(&msg);
+    let flags = rtnl_msg_ext::flags(&msg);
+    let attrs = rtnl_msg_ext::attrs(&msg);
+    let link_layer_type = rtnl_msg_ext::link_layer_type(&msg);
+
+    let mut base = LinkAttrs::new();
+    base.index = index;
+    base.raw_flags = flags;
+    base.encap_type = nl_linux::encap_type(link_layer_type);
+    base.net_ns_id = -1;
+    if flags & IFF_PROMISC != 0 {
+        base.promisc = 1;
+    }
+    if flags & IFF_ALLMULTI != 0 {
+        base.allmulti = 1;
+    }
+    if flags & IFF_MULTICAST != 0 {
+        base.multi = 1;
+    }
+
+    let mut link_kind: Option<LinkKind> = None;
+    for attr in attrs {
+        match attr {
+            Nla::IfName(name) => {
+                base.name = name.clone();
+            }
+            Nla::Address(addr) => {
+                let hardware_addr = if addr.len() == 6 {
+                    MacAddr6::new(addr[0], addr[1], addr[2], addr[3], addr[4], addr[5])
+                } else {
+                    MacAddr6::default()
+                };
+                base.hardware_addr = Some(hardware_addr);
+            }
+            Nla::Mtu(mtu) => {
+                base.mtu = *mtu;
+            }
+            Nla::Link(link) => {
+                base.parent_index = *link;
+            }
+            Nla::Master(master) => {
+                base.master_index = *master;
+            }
+
+            Nla::TxQueueLen(len) => {
+                base.tx_q_len = *len;
+            }
+            Nla::IfAlias(alias) => {
+                base.alias = alias.clone();
+            }
+            Nla::Stats(_stats) => {}
+            Nla::Stats64(stats) => {
+                let buffer = Stats64Buffer::new(stats);
+                base.statistics = Some(Stats64::parse(&buffer).unwrap());
+            }
+            Nla::Xdp(xdp_data) => {
+                // todo
+                debug!("xdp_data: {:?}", xdp_data);
+            }
+            Nla::OperState(state) => {
+                base.oper_state = *state;
+            }
+            Nla::PhysSwitchId(vec) => {
+                if vec.len() == 4 {
+                    base.phys_switch_id = u32::from_ne_bytes([vec[0], vec[1], vec[2], vec[3]]);
+                }
+            }
+            Nla::NetnsId(id) => {
+                base.net_ns_id = *id;
+            }
+            Nla::GsoMaxSegs(segs) => {
+                base.gso_max_segs = *segs;
+            }
+            Nla::GsoMaxSize(size) => {
+                base.gso_max_size = *size;
+            }
+            Nla::NumTxQueues(queues) => {
+                base.num_tx_queues = *queues;
+            }
+            Nla::NumRxQueues(queues) => {
+                base.num_rx_queues = *queues;
+            }
+            Nla::Group(group) => {
+                base.group = *group;
+            }
+            Nla::VfInfoList(_data) => {
+                // base.vfs = data.to_vec();
+            }
+            Nla::Info(infos) => {
+                for info in infos {
+                    // debug!("info: {:?}", info);
+                    match info {
+                        Info::Kind(kind) => match kind {
+                            InfoKind::Bridge => {
+                                link_kind = Some(LinkKind::Bridge(Bridge::default()));
+                            }
+                            InfoKind::Veth => {
+                                link_kind = Some(LinkKind::Veth(Veth::default()));
+                            }
                             InfoKind::Tun => {
                                 link_kind = Some(LinkKind::Tuntap(Tuntap::default()));
                             }
                             InfoKind::Vxlan => {
                                 link_kind = Some(LinkKind::Vxlan(Vxlan::default()));
                             }
+                            InfoKind::Vlan => {
+                                link_kind = Some(LinkKind::Vlan(Vlan::default()));
+                            }
+                            InfoKind::MacVlan => {
+                                link_kind = Some(LinkKind::Macvlan(Macvlan::default()));
+                            }
+                            InfoKind::MacVtap => {
+                                link_kind = Some(LinkKind::Macvtap(Macvtap::default()));
+                            }
+                            InfoKind::IpVlan => {
+                                link_kind = Some(LinkKind::Ipvlan(Ipvlan::default()));
+                            }
+                            InfoKind::Bond => {
+                                link_kind = Some(LinkKind::Bond(Bond::default()));
+                            }
+                            InfoKind::Dummy => {
+                                link_kind = Some(LinkKind::Dummy(Dummy::default()));
+                            }
+                            InfoKind::Vrf => {
+                                link_kind = Some(LinkKind::Vrf(Vrf::default()));
+                            }
+                            InfoKind::Wireguard => {
+                                link_kind = Some(LinkKind::Wireguard(Wireguard::default()));
+                            }
+                            InfoKind::GreTun => {
+                                link_kind = Some(LinkKind::Gre(Gre::default()));
+                            }
+                            InfoKind::GreTap => {
+                                link_kind = Some(LinkKind::Gretap(Gre::default()));
+                            }
+                            InfoKind::GreTun6 => {
+                                link_kind = Some(LinkKind::Ip6gre(Gre::default()));
+                            }
+                            InfoKind::GreTap6 => {
+                                link_kind = Some(LinkKind::Ip6gretap(Gre::default()));
+                            }
+                            InfoKind::IpTun => {
+                                link_kind = Some(LinkKind::Ipip(Iptun::default()));
+                            }
+                            InfoKind::SitTun => {
+                                link_kind = Some(LinkKind::Sit(Iptun::default()));
+                            }
+                            InfoKind::Other(other) => match other.as_str() {
+                                "ipvtap" => link_kind = Some(LinkKind::Ipvtap(Ipvtap::default())),
+                                "erspan" => link_kind = Some(LinkKind::Erspan(Gre::default())),
+                                "ip6tnl" => link_kind = Some(LinkKind::Ip6tnl(Iptun::default())),
+                                "geneve" => link_kind = Some(LinkKind::Geneve(Geneve::default())),
+                                _ => debug!("info kind: {:?}", kind),
+                            },
                             _ => {
+                                // kinds without a LinkKind are returned as LinkKind::Device
                                 debug!("info kind: {:?}", kind);
-                                unimplemented!("info kind: {:?}", kind)
                             }
                         },
                         Info::Data(data) => {
                             match data {
-                                InfoData::Bridge(_br) => {
-                                    // info!("bridge data: {:?}", br)
+                                InfoData::Bridge(nlas) => {
+                                    link_kind = Some(LinkKind::Bridge(bridge_deserialize(nlas)));
+                                }
+                                InfoData::Tun(bytes) => {
+                                    link_kind = Some(LinkKind::Tuntap(tuntap::tuntap_deserialize(&utils::parse_nlas(bytes))));
+                                }
+                                InfoData::Vlan(nlas) => {
+                                    link_kind = Some(LinkKind::Vlan(vlan_deserialize(nlas)));
                                 }
-                                InfoData::Tun(_tun) => {
-                                    // info!("tun data: {:?}", tun)
+                                InfoData::MacVlan(nlas) => {
+                                    link_kind = Some(LinkKind::Macvlan(macvlan_deserialize(nlas)));
+                                }
+                                InfoData::MacVtap(nlas) => {
+                                    let nlas: Vec<InfoMacVlan> = convert_macvlan_nlas(nlas);
+                                    link_kind = Some(LinkKind::Macvtap(macvlan_deserialize(&nlas)));
+                                }
+                                InfoData::IpVlan(nlas) => {
+                                    link_kind = Some(LinkKind::Ipvlan(ipvlan_deserialize(nlas)));
+                                }
+                                InfoData::Bond(nlas) => {
+                                    link_kind = Some(LinkKind::Bond(bond_deserialize(nlas)));
+                                }
+                                InfoData::Vrf(nlas) => {
+                                    let mut vrf = Vrf::default();
+                                    for nla in nlas {
+                                        if let InfoVrf::TableId(table) = nla {
+                                            vrf.table = *table;
+                                        }
+                                    }
+                                    link_kind = Some(LinkKind::Vrf(vrf));
+                                }
+                                InfoData::GreTun(bytes)
+                                | InfoData::GreTap(bytes)
+                                | InfoData::GreTun6(bytes)
+                                | InfoData::GreTap6(bytes)
+                                | InfoData::IpTun(bytes)
+                                | InfoData::SitTun(bytes)
+                                | InfoData::Other(bytes) => {
+                                    if let Some(link_kind) = &mut link_kind {
+                                        raw_info_data_deserialize(link_kind, bytes);
+                                    }
                                 }
                                 _ => {
                                     // println!("data: {:?}", data);
                                 }
                             }
                         }
+                        Info::PortData(InfoPortData::BondPort(nlas)) => {
+                            base.slave = Some(LinkSlave::Bond(bond_slave_deserialize(nlas)));
+                        }
+                        Info::PortData(InfoPortData::Other(bytes))
+                            if infos.contains(&Info::PortKind(InfoPortKind::Other("bridge".to_string()))) => {
+                            let nlas: Vec<DefaultNla> = utils::parse_nlas(bytes);
+                            base.slave = Some(LinkSlave::Bridge(bridge_slave_deserialize(&nlas)));
+                        }
                         Info::PortData(data) => {
                             info!("port data: {:?}", data)
                         }
@@ -684,7 +1945,7 @@ fn link_deserialize(msg: &RtnlMessage) -> anyhow::Result<Link> {
                             info!("port kind: {:?}", data)
                         }
                         _ => {
-                            unimplemented!("info: {:?}", info)
+                            debug!("info: {:?}", info)
                         }
                     }
                 }
@@ -692,6 +1953,19 @@ fn link_deserialize(msg: &RtnlMessage) -> anyhow::Result<Link> {
             Nla::NetNsFd(fd) => {
                 base.net_ns_id = *fd;
             }
+            Nla::AfSpecInet(specs) => {
+                for spec in specs {
+                    match spec {
+                        AfSpecInet::Inet(nlas) => {
+                            base.inet = Some(link_inet_deserialize(nlas));
+                        }
+                        AfSpecInet::Inet6(nlas) => {
+                            base.inet6 = Some(link_inet6_deserialize(nlas));
+                        }
+                        _ => {}
+                    }
+                }
+            }
             _ => {
                 // println!("attr: {:?}", attr);
             }
@@ -707,6 +1981,42 @@ fn link_deserialize(msg: &RtnlMessage) -> anyhow::Result<Link> {
     });
 }
 
+fn link_inet_deserialize(nlas: &[Inet]) -> LinkInet {
+    let mut inet = LinkInet::default();
+    for nla in nlas {
+        if let Inet::DevConf(bytes) = nla {
+            inet.devconf = InetDevConfBuffer::new_checked(bytes.as_slice())
+                .and_then(|buf| InetDevConf::parse(&buf))
+                .ok();
+        }
+    }
+    inet
+}
+
+fn link_inet6_deserialize(nlas: &[Inet6]) -> LinkInet6 {
+    let mut inet6 = LinkInet6::default();
+    for nla in nlas {
+        match nla {
+            Inet6::Flags(flags) => {
+                inet6.flags = *flags;
+            }
+            Inet6::Token(token) => {
+                inet6.token = Some(Ipv6Addr::from(*token));
+            }
+            Inet6::AddrGenMode(mode) => {
+                inet6.addr_gen_mode = AddrGenMode::try_from(*mode).ok();
+            }
+            Inet6::DevConf(bytes) => {
+                inet6.devconf = Inet6DevConfBuffer::new_checked(bytes.as_slice())
+                    .and_then(|buf| Inet6DevConf::parse(&buf))
+                    .ok();
+            }
+            _ => {}
+        }
+    }
+    inet6
+}
+
 pub fn set_promisc_on(index: LinkIndex) -> anyhow::Result<()> {
     let mut msg = LinkMessage::default();
     msg.header.index = index;
@@ -717,16 +2027,59 @@ pub fn set_promisc_on(index: LinkIndex) -> anyhow::Result<()> {
     Ok(())
 }
 
-pub fn link_set_mtu(index: LinkIndex, mtu: u32) -> anyhow::Result<()> {
+pub fn link_set_mtu(index: LinkIndex, mtu: u32) -> anyhow::Result<()> {
+    let mut msg = LinkMessage::default();
+    msg.header.index = index;
+    msg.header.flags |= IFF_MULTICAST;
+    msg.header.change_mask |= IFF_MULTICAST;
+    msg.nlas.push(Nla::Mtu(mtu));
+    NetlinkHandle::new().execute(RtnlMessage::SetLink(msg), NLM_F_ACK)?;
+    Ok(())
+}
+
+// link_set_token sets the IPv6 tokenized interface identifier of the link device.
+// Equivalent to: `ip token set $token dev $link`
+pub fn link_set_token(index: LinkIndex, token: Ipv6Addr) -> anyhow::Result<()> {
+    link_set_inet6(index, Inet6::Token(token.octets()))
+}
+
+// link_set_addr_gen_mode sets the IPv6 link-local address generation mode of the link device.
+// Equivalent to: `ip link set $link addrgenmode $mode`
+pub fn link_set_addr_gen_mode(index: LinkIndex, mode: AddrGenMode) -> anyhow::Result<()> {
+    link_set_inet6(index, Inet6::AddrGenMode(mode.into()))
+}
+
+fn link_set_inet6(index: LinkIndex, nla: Inet6) -> anyhow::Result<()> {
+    let mut msg = LinkMessage::default();
+    msg.header.index = index;
+    msg.nlas.push(Nla::AfSpecInet(vec![AfSpecInet::Inet6(vec![nla])]));
+    NetlinkHandle::new().execute(RtnlMessage::SetLink(msg), NLM_F_ACK)?;
+    Ok(())
+}
+
+// link_set_inet_devconf sets an IPv4 devconf value of the link device, `id` is one of
+// the IPV4_DEVCONF_* constants. The kernel does not allow IPv6 devconf to be set over netlink.
+// Equivalent to: `sysctl -w net.ipv4.conf.$link.$conf=$value`
+pub fn link_set_inet_devconf(index: LinkIndex, id: u16, value: u32) -> anyhow::Result<()> {
+    let conf = vec![DefaultNla::new(id, value.to_ne_bytes().to_vec())];
+    let mut bytes = vec![0u8; conf.as_slice().buffer_len()];
+    conf.as_slice().emit(&mut bytes);
+
     let mut msg = LinkMessage::default();
     msg.header.index = index;
-    msg.header.flags |= IFF_MULTICAST;
-    msg.header.change_mask |= IFF_MULTICAST;
-    msg.nlas.push(Nla::Mtu(mtu));
+    msg.nlas.push(Nla::AfSpecInet(vec![AfSpecInet::Inet(vec![Inet::DevConf(bytes)])]));
     NetlinkHandle::new().execute(RtnlMessage::SetLink(msg), NLM_F_ACK)?;
     Ok(())
 }
 
+pub fn link_set_ip_forwarding(index: LinkIndex, enable: bool) -> anyhow::Result<()> {
+    link_set_inet_devconf(index, IPV4_DEVCONF_FORWARDING, enable as u32)
+}
+
+pub fn link_set_rp_filter(index: LinkIndex, mode: u32) -> anyhow::Result<()> {
+    link_set_inet_devconf(index, IPV4_DEVCONF_RP_FILTER, mode)
+}
+
 #[cfg(test)]
 mod tests {
     use std::error::Error;
@@ -735,11 +2088,29 @@ mod tests {
 
     use rand::Rng;
 
-    use crate::addr_list;
-    use crate::nl_type::{Bridge, FAMILY_V4};
+    use crate::{addr_list, tuntap_add};
+    use crate::nl_linux::GRE_SEQ;
+    use crate::nl_type::{Bridge, FAMILY_V4, TUNTAP_MODE_TAP, TUNTAP_MULTI_QUEUE_DEFAULTS, TUNTAP_VNET_HDR};
 
     use super::*;
 
+    macro_rules! expect_kind {
+        ($link:expr, $kind:path) => {
+            match &$link.link_kind {
+                $kind(kind) => kind,
+                kind => panic!("unexpected link kind: {:?}", kind),
+            }
+        };
+    }
+
+    // add_fetch_del creates `link`, reads it back by name and deletes it
+    fn add_fetch_del(link: &Link) -> anyhow::Result<Link> {
+        link_add(link)?;
+        let found = link_by_name(&link.link_attrs.name)?.unwrap();
+        link_del(found.as_index())?;
+        Ok(found)
+    }
+
     #[test]
     fn test_link_add_del_with_index() {
         let result = link_list();
@@ -804,6 +2175,118 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_add_link_bridge_options() -> anyhow::Result<()> {
+        let name = "br235";
+        let link = Link {
+            link_attrs: LinkAttrs {
+                name: name.to_string(),
+                ..Default::default()
+            },
+            link_kind: LinkKind::Bridge(Bridge {
+                ageing_time: Some(20000),
+                hello_time: Some(300),
+                forward_delay: Some(1000),
+                max_age: Some(1500),
+                stp_state: Some(1),
+                priority: Some(4096),
+                group_fwd_mask: Some(0x8),
+                multicast_snooping: Some(true),
+                multicast_router: Some(2),
+                multicast_querier: Some(true),
+                multicast_igmp_version: Some(3),
+                multicast_mld_version: Some(2),
+                multicast_last_member_count: Some(3),
+                multicast_query_interval: Some(6000),
+                multicast_membership_interval: Some(30000),
+                ..Default::default()
+            }),
+        };
+        let found = add_fetch_del(&link)?;
+
+        let bridge = expect_kind!(found, LinkKind::Bridge);
+        assert_eq!(bridge.ageing_time, Some(20000));
+        assert_eq!(bridge.hello_time, Some(300));
+        assert_eq!(bridge.forward_delay, Some(1000));
+        assert_eq!(bridge.max_age, Some(1500));
+        assert_eq!(bridge.stp_state, Some(1));
+        assert_eq!(bridge.priority, Some(4096));
+        assert_eq!(bridge.group_fwd_mask, Some(0x8));
+        assert_eq!(bridge.multicast_snooping, Some(true));
+        assert_eq!(bridge.multicast_router, Some(2));
+        assert_eq!(bridge.multicast_querier, Some(true));
+        assert_eq!(bridge.multicast_igmp_version, Some(3));
+        assert_eq!(bridge.multicast_mld_version, Some(2));
+        assert_eq!(bridge.multicast_last_member_count, Some(3));
+        assert_eq!(bridge.multicast_query_interval, Some(6000));
+        assert_eq!(bridge.multicast_membership_interval, Some(30000));
+        Ok(())
+    }
+
+    #[test]
+    fn test_link_set_bridge_slave() -> anyhow::Result<()> {
+        let bridge = Link {
+            link_attrs: LinkAttrs {
+                name: "br236".to_string(),
+                ..Default::default()
+            },
+            link_kind: LinkKind::Bridge(Bridge::default()),
+        };
+        link_add(&bridge)?;
+        let mut port = Link {
+            link_attrs: LinkAttrs {
+                name: "tap236".to_string(),
+                ..Default::default()
+            },
+            link_kind: LinkKind::Tuntap(Tuntap {
+                mode: TUNTAP_MODE_TAP,
+                ..Default::default()
+            }),
+        };
+        tuntap_add(&mut port)?;
+        let bridge = link_by_name("br236")?.unwrap();
+        let port = link_by_name("tap236")?.unwrap();
+        link_set_master(&port, &bridge)?;
+        let result = link_set_bridge_slave(&port, &BridgeSlave {
+            priority: Some(16),
+            cost: Some(200),
+            hairpin: Some(true),
+            guard: Some(true),
+            root_block: Some(true),
+            fast_leave: Some(true),
+            learning: Some(false),
+            unicast_flood: Some(false),
+            multicast_flood: Some(false),
+            broadcast_flood: Some(false),
+            proxy_arp: Some(true),
+            isolated: Some(true),
+            ..Default::default()
+        });
+        let found = link_by_name("tap236")?.unwrap();
+        link_del(port.as_index())?;
+        link_del(bridge.as_index())?;
+        result?;
+
+        let Some(LinkSlave::Bridge(slave)) = &found.attrs().slave else {
+            panic!("missing bridge slave data: {:?}", found.attrs().slave);
+        };
+        assert_eq!(slave.state, Some(BridgePortState::Disabled));
+        assert_eq!(slave.priority, Some(16));
+        assert_eq!(slave.cost, Some(200));
+        assert_eq!(slave.hairpin, Some(true));
+        assert_eq!(slave.guard, Some(true));
+        assert_eq!(slave.root_block, Some(true));
+        assert_eq!(slave.fast_leave, Some(true));
+        assert_eq!(slave.learning, Some(false));
+        assert_eq!(slave.unicast_flood, Some(false));
+        assert_eq!(slave.multicast_flood, Some(false));
+        assert_eq!(slave.broadcast_flood, Some(false));
+        assert_eq!(slave.proxy_arp, Some(true));
+        assert_eq!(slave.isolated, Some(true));
+        assert_eq!(slave.neigh_suppress, Some(false));
+        Ok(())
+    }
+
     #[test]
     fn test_add_link_veth() -> anyhow::Result<()> {
         let name = "vethhost";
@@ -924,6 +2407,54 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_set_addr_gen_mode() -> anyhow::Result<()> {
+        let link_id = "br234".try_as_index()?.unwrap();
+        link_set_addr_gen_mode(link_id, AddrGenMode::None)?;
+        let link = link_by_index(link_id)?.unwrap();
+        let inet6 = link.attrs().inet6.as_ref().unwrap();
+        assert_eq!(inet6.addr_gen_mode, Some(AddrGenMode::None));
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_token() -> anyhow::Result<()> {
+        // dummy links are NOARP, which the kernel refuses tokens for
+        let link = Link {
+            link_attrs: LinkAttrs {
+                name: "veth235".to_string(),
+                ..Default::default()
+            },
+            link_kind: LinkKind::Veth(Veth {
+                peer_name: "veth235p".to_string(),
+                peer_namespace: Namespace::NsPid(std::process::id()),
+                ..Default::default()
+            }),
+        };
+        link_add(&link)?;
+        let link_id = link_by_name("veth235")?.unwrap().as_index();
+
+        let token: Ipv6Addr = "::1234".parse()?;
+        let result = link_set_token(link_id, token).and_then(|_| link_by_index(link_id));
+        link_del(link_id)?;
+
+        let link = result?.unwrap();
+        assert_eq!(link.attrs().inet6.as_ref().unwrap().token, Some(token));
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_inet_devconf() -> anyhow::Result<()> {
+        let link_id = "br234".try_as_index()?.unwrap();
+        link_set_ip_forwarding(link_id, true)?;
+        link_set_rp_filter(link_id, 2)?;
+        let link = link_by_index(link_id)?.unwrap();
+        let devconf = link.attrs().inet.as_ref().unwrap().devconf.unwrap();
+        assert_eq!(devconf.forwarding, 1);
+        assert_eq!(devconf.rp_filter, 2);
+        Ok(())
+    }
+
     #[test]
     fn test_new_vxlan() {
         let eth0_link = link_by_name("eth0").unwrap().unwrap();
@@ -950,6 +2481,544 @@ mod tests {
     }
 
 
+    #[test]
+    fn test_add_link_vxlan_round_trip() -> anyhow::Result<()> {
+        let vtep_dev_index = "eth0".try_as_index()?.unwrap();
+        let link = Link {
+            link_attrs: LinkAttrs {
+                name: "vxlan234".to_string(),
+                ..Default::default()
+            },
+            link_kind: LinkKind::Vxlan(Vxlan {
+                vxlan_id: 234,
+                vtep_dev_index,
+                src_addr: Some("10.10.10.1".parse()?),
+                group: Some("239.1.1.1".parse()?),
+                ttl: 16,
+                proxy: true,
+                l2miss: true,
+                udp_csum: true,
+                age: 120,
+                limit: 1000,
+                port: 4789,
+                port_low: 5000,
+                port_high: 6000,
+                df: TunnelDf::Inherit,
+                remcsum_tx: true,
+                remcsum_nopartial: true,
+                gbp: true,
+                ..Default::default()
+            }),
+        };
+        let found = add_fetch_del(&link)?;
+
+        let vxlan = expect_kind!(found, LinkKind::Vxlan);
+        assert_eq!(vxlan.vxlan_id, 234);
+        assert_eq!(vxlan.vtep_dev_index, vtep_dev_index);
+        assert_eq!(vxlan.src_addr, Some("10.10.10.1".parse()?));
+        assert_eq!(vxlan.group, Some("239.1.1.1".parse()?));
+        assert_eq!(vxlan.ttl, 16);
+        assert!(!vxlan.learning && vxlan.proxy && vxlan.l2miss && !vxlan.l3miss);
+        assert!(vxlan.udp_csum && vxlan.remcsum_tx);
+        assert_eq!((vxlan.age, vxlan.no_age, vxlan.limit), (120, false, 1000));
+        assert_eq!((vxlan.port, vxlan.port_low, vxlan.port_high), (4789, 5000, 6000));
+        assert_eq!(vxlan.df, TunnelDf::Inherit);
+        assert!(vxlan.gbp && vxlan.remcsum_nopartial && !vxlan.gpe);
+        assert!(!vxlan.ttl_inherit);
+
+        // the flow label only applies to ipv6
+        let link = Link {
+            link_attrs: LinkAttrs {
+                name: "vxlan235".to_string(),
+                ..Default::default()
+            },
+            link_kind: LinkKind::Vxlan(Vxlan {
+                vxlan_id: 235,
+                group: Some("fd00::235".parse()?),
+                label: 0x12345,
+                udp6_zero_csum_tx: true,
+                ttl_inherit: true,
+                ..Default::default()
+            }),
+        };
+        let found = add_fetch_del(&link)?;
+
+        let vxlan = expect_kind!(found, LinkKind::Vxlan);
+        assert_eq!(vxlan.group, Some("fd00::235".parse()?));
+        assert_eq!(vxlan.label, 0x12345);
+        assert!(vxlan.udp6_zero_csum_tx && vxlan.ttl_inherit);
+
+        let link = Link {
+            link_attrs: LinkAttrs {
+                name: "vxlan236".to_string(),
+                ..Default::default()
+            },
+            link_kind: LinkKind::Vxlan(Vxlan {
+                flow_based: true,
+                vni_filter: true,
+                learning: true,
+                ..Default::default()
+            }),
+        };
+        let found = add_fetch_del(&link)?;
+
+        let vxlan = expect_kind!(found, LinkKind::Vxlan);
+        assert!(vxlan.flow_based && vxlan.vni_filter && vxlan.learning);
+
+        // gpe devices carry l3 packets and only allow a few other options
+        let link = Link {
+            link_attrs: LinkAttrs {
+                name: "vxlan237".to_string(),
+                ..Default::default()
+            },
+            link_kind: LinkKind::Vxlan(Vxlan {
+                flow_based: true,
+                gpe: true,
+                port: 4790,
+                ..Default::default()
+            }),
+        };
+        let found = add_fetch_del(&link)?;
+
+        let vxlan = expect_kind!(found, LinkKind::Vxlan);
+        assert!(vxlan.flow_based && vxlan.gpe && !vxlan.gbp && !vxlan.vni_filter);
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_link_vlan() -> anyhow::Result<()> {
+        let parent_index = "eth0".try_as_index()?.unwrap();
+        let link = Link {
+            link_attrs: LinkAttrs {
+                name: "vlan234".to_string(),
+                parent_index,
+                ..Default::default()
+            },
+            link_kind: LinkKind::Vlan(Vlan {
+                vlan_id: 234,
+                vlan_protocol: Some(VlanProtocol::Dot1AD),
+                reorder_hdr: Some(false),
+                mvrp: Some(true),
+                ingress_qos_map: BTreeMap::from([(1, 2)]),
+                egress_qos_map: BTreeMap::from([(3, 4)]),
+                ..Default::default()
+            }),
+        };
+        let found = add_fetch_del(&link)?;
+
+        assert_eq!(found.attrs().parent_index, parent_index);
+        let vlan = expect_kind!(found, LinkKind::Vlan);
+        assert_eq!(vlan.vlan_id, 234);
+        assert_eq!(vlan.vlan_protocol, Some(VlanProtocol::Dot1AD));
+        assert_eq!(vlan.reorder_hdr, Some(false));
+        assert_eq!(vlan.mvrp, Some(true));
+        assert_eq!(vlan.gvrp, Some(false));
+        assert_eq!(vlan.ingress_qos_map, BTreeMap::from([(1, 2)]));
+        assert_eq!(vlan.egress_qos_map, BTreeMap::from([(3, 4)]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_link_macvlan() -> anyhow::Result<()> {
+        let parent_index = "eth0".try_as_index()?.unwrap();
+        let addr1: MacAddr6 = "3a:91:c1:3f:ee:01".parse()?;
+        let addr2: MacAddr6 = "3a:91:c1:3f:ee:02".parse()?;
+        let link = Link {
+            link_attrs: LinkAttrs {
+                name: "macvlan234".to_string(),
+                parent_index,
+                ..Default::default()
+            },
+            link_kind: LinkKind::Macvlan(Macvlan {
+                mode: Some(MacvlanMode::Source),
+                mac_addrs: vec![addr1],
+                ..Default::default()
+            }),
+        };
+        link_add(&link)?;
+        let found = link_by_name("macvlan234")?.unwrap();
+        macvlan_mac_addr_add(&found, addr2)?;
+        let with_addr2 = link_by_name("macvlan234")?.unwrap();
+        macvlan_mac_addr_flush(&found)?;
+        let flushed = link_by_name("macvlan234")?.unwrap();
+        link_del(found.as_index())?;
+
+        assert_eq!(found.attrs().parent_index, parent_index);
+        let macvlan = expect_kind!(found, LinkKind::Macvlan);
+        assert_eq!(macvlan.mode, Some(MacvlanMode::Source));
+        assert_eq!(macvlan.mac_addrs, vec![addr1]);
+        assert_eq!(expect_kind!(with_addr2, LinkKind::Macvlan).mac_addrs.len(), 2);
+        assert!(expect_kind!(flushed, LinkKind::Macvlan).mac_addrs.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_link_macvtap() -> anyhow::Result<()> {
+        let link = Link {
+            link_attrs: LinkAttrs {
+                name: "macvtap234".to_string(),
+                parent_index: "eth0".try_as_index()?.unwrap(),
+                ..Default::default()
+            },
+            link_kind: LinkKind::Macvtap(Macvtap {
+                mode: Some(MacvlanMode::Bridge),
+                nopromisc: true,
+                ..Default::default()
+            }),
+        };
+        let found = add_fetch_del(&link)?;
+
+        let macvtap = expect_kind!(found, LinkKind::Macvtap);
+        assert_eq!(macvtap.mode, Some(MacvlanMode::Bridge));
+        assert!(macvtap.nopromisc);
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_link_tuntap() -> anyhow::Result<()> {
+        let mut link = Link {
+            link_attrs: LinkAttrs {
+                name: "tap234".to_string(),
+                ..Default::default()
+            },
+            link_kind: LinkKind::Tuntap(Tuntap {
+                mode: TUNTAP_MODE_TAP,
+                flags: TUNTAP_MULTI_QUEUE_DEFAULTS | TUNTAP_VNET_HDR,
+                queues: 2,
+                ..Default::default()
+            }),
+        };
+        tuntap_add(&mut link)?;
+        let found = link_by_name("tap234")?.unwrap();
+        link_del(found.as_index())?;
+
+        assert_eq!(expect_kind!(link, LinkKind::Tuntap).fds.len(), 2);
+        assert_eq!(found.link_attrs.index, link.link_attrs.index);
+        assert_eq!(found.link_attrs.name, "tap234");
+
+        let found = expect_kind!(found, LinkKind::Tuntap);
+        assert_eq!(found.mode, TUNTAP_MODE_TAP);
+        assert_eq!(found.flags, TUNTAP_MULTI_QUEUE_DEFAULTS | TUNTAP_VNET_HDR);
+        assert_eq!(found.queues, 2);
+        assert_eq!(found.disabled_queues, 0);
+        assert!(!found.non_persist);
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_link_device() -> anyhow::Result<()> {
+        // lo has no link kind and reads back as a plain device
+        let lo = link_by_name("lo")?.unwrap();
+        assert!(matches!(lo.link_kind, LinkKind::Device));
+        let err = link_add(&lo).unwrap_err();
+        assert_eq!(err.to_string(), "cannot create a link of kind device");
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_link_ipvlan() -> anyhow::Result<()> {
+        let parent_index = "eth0".try_as_index()?.unwrap();
+        for (name, link_kind) in [
+            ("ipvlan234", LinkKind::Ipvlan(Ipvlan { mode: Some(IpvlanMode::L3S), flag: Some(IpvlanFlag::Private) })),
+            ("ipvtap234", LinkKind::Ipvtap(Ipvtap { mode: Some(IpvlanMode::L2), flag: Some(IpvlanFlag::Vepa) })),
+        ] {
+            let link = Link {
+                link_attrs: LinkAttrs {
+                    name: name.to_string(),
+                    parent_index,
+                    ..Default::default()
+                },
+                link_kind,
+            };
+            let found = add_fetch_del(&link)?;
+
+            match (&link.link_kind, &found.link_kind) {
+                (LinkKind::Ipvlan(want), LinkKind::Ipvlan(got)) | (LinkKind::Ipvtap(want), LinkKind::Ipvtap(got)) => {
+                    assert_eq!(got.mode, want.mode);
+                    assert_eq!(got.flag, want.flag);
+                }
+                _ => panic!("unexpected link kind: {:?}", found.link_kind),
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_link_bond() -> anyhow::Result<()> {
+        let link = Link {
+            link_attrs: LinkAttrs {
+                name: "bond234".to_string(),
+                ..Default::default()
+            },
+            link_kind: LinkKind::Bond(Bond {
+                mode: Some(BondMode::Ieee8023Ad),
+                miimon: Some(100),
+                up_delay: Some(200),
+                xmit_hash_policy: Some(BondXmitHashPolicy::Layer34),
+                lacp_rate: Some(BondLacpRate::Fast),
+                ad_select: Some(BondAdSelect::Bandwidth),
+                min_links: Some(1),
+                all_slaves_active: Some(true),
+                ..Default::default()
+            }),
+        };
+        link_add(&link)?;
+        let bond = link_by_name("bond234")?.unwrap();
+        let mut slaves = Vec::new();
+        for name in ["dummy2340", "dummy2341"] {
+            link_add(&Link {
+                link_attrs: LinkAttrs {
+                    name: name.to_string(),
+                    ..Default::default()
+                },
+                link_kind: LinkKind::Dummy(Dummy::default()),
+            })?;
+            let slave = link_by_name(name)?.unwrap();
+            link_set_master(&slave, &bond)?;
+            slaves.push(link_by_name(name)?.unwrap());
+        }
+        let bond = link_by_name("bond234")?.unwrap();
+        for slave in &slaves {
+            link_del(slave.as_index())?;
+        }
+        link_del(bond.as_index())?;
+
+        let info = expect_kind!(bond, LinkKind::Bond);
+        assert_eq!(info.mode, Some(BondMode::Ieee8023Ad));
+        assert_eq!(info.miimon, Some(100));
+        assert_eq!(info.up_delay, Some(200));
+        assert_eq!(info.xmit_hash_policy, Some(BondXmitHashPolicy::Layer34));
+        assert_eq!(info.lacp_rate, Some(BondLacpRate::Fast));
+        assert_eq!(info.ad_select, Some(BondAdSelect::Bandwidth));
+        assert_eq!(info.all_slaves_active, Some(true));
+        for slave in &slaves {
+            assert_eq!(slave.attrs().master_index, bond.as_index());
+            let Some(LinkSlave::Bond(slave)) = &slave.attrs().slave else {
+                panic!("missing bond slave data: {:?}", slave.attrs().slave);
+            };
+            // aggregator_id is only reported once the bond is up and its 802.3ad state
+            // machine selected an aggregator, test_bond_slave_deserialize covers it
+            assert!(slave.perm_hardware_addr.is_some());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_bond_slave_deserialize() {
+        let nlas = vec![
+            InfoBondPort::PermHwaddr(vec![0x02, 0, 0, 0, 0, 0x01]),
+            InfoBondPort::Other(DefaultNla::new(IFLA_BOND_PORT_AD_AGGREGATOR_ID, 3u16.to_ne_bytes().to_vec())),
+            InfoBondPort::Other(DefaultNla::new(IFLA_BOND_PORT_AD_ACTOR_OPER_PORT_STATE, vec![0x3d])),
+            InfoBondPort::Other(DefaultNla::new(IFLA_BOND_PORT_AD_PARTNER_OPER_PORT_STATE, 0x3fu16.to_ne_bytes().to_vec())),
+        ];
+        let slave = bond_slave_deserialize(&nlas);
+        assert_eq!(slave.perm_hardware_addr, Some(MacAddr6::new(0x02, 0, 0, 0, 0, 0x01)));
+        assert_eq!(slave.aggregator_id, Some(3));
+        assert_eq!(slave.ad_actor_oper_port_state, Some(0x3d));
+        assert_eq!(slave.ad_partner_oper_port_state, Some(0x3f));
+    }
+
+    #[test]
+    fn test_add_link_vrf() -> anyhow::Result<()> {
+        let vrf = Link {
+            link_attrs: LinkAttrs {
+                name: "vrf234".to_string(),
+                ..Default::default()
+            },
+            link_kind: LinkKind::Vrf(Vrf { table: 234 }),
+        };
+        link_add(&vrf)?;
+        let vrf = link_by_name("vrf234")?.unwrap();
+        let mut tap = Link {
+            link_attrs: LinkAttrs {
+                name: "tap239".to_string(),
+                master_index: vrf.link_attrs.index,
+                ..Default::default()
+            },
+            link_kind: LinkKind::Tuntap(Tuntap {
+                mode: TUNTAP_MODE_TAP,
+                ..Default::default()
+            }),
+        };
+        let result = tuntap_add(&mut tap).and_then(|_| {
+            let tap = link_by_name("tap239")?.unwrap();
+            link_del(tap.as_index())?;
+            link_vrf(&tap)
+        });
+        link_del(vrf.as_index())?;
+
+        assert_eq!(expect_kind!(vrf, LinkKind::Vrf).table, 234);
+        assert_eq!(result?.map(|it| it.link_attrs.index), Some(vrf.link_attrs.index));
+        Ok(())
+    }
+
+    #[test]
+    fn test_geneve_nlas_round_trip() {
+        let geneve = Geneve {
+            id: 234,
+            remote: Some("fd00::234".parse().unwrap()),
+            ttl_inherit: true,
+            tos: 0x10,
+            label: 0x12345,
+            port: 6081,
+            udp6_zero_csum_rx: true,
+            df: TunnelDf::Set,
+            inner_proto_inherit: true,
+            ..Default::default()
+        };
+        let bytes = utils::emit_nlas(&geneve_nlas(&geneve));
+        let found = geneve_deserialize(&utils::parse_nlas(&bytes));
+        assert_eq!(found.id, 234);
+        assert_eq!(found.remote, geneve.remote);
+        assert!(found.ttl_inherit);
+        assert_eq!((found.tos, found.label, found.port), (0x10, 0x12345, 6081));
+        assert!(!found.udp6_zero_csum_tx && found.udp6_zero_csum_rx);
+        assert_eq!(found.df, TunnelDf::Set);
+        assert!(found.inner_proto_inherit && !found.collect_metadata);
+    }
+
+    #[test]
+    fn test_add_link_geneve() -> anyhow::Result<()> {
+        let link = Link {
+            link_attrs: LinkAttrs {
+                name: "geneve234".to_string(),
+                ..Default::default()
+            },
+            link_kind: LinkKind::Geneve(Geneve {
+                id: 234,
+                remote: Some("10.0.0.2".parse()?),
+                ttl: 64,
+                port: 6081,
+                udp_csum: true,
+                ..Default::default()
+            }),
+        };
+        let found = add_fetch_del(&link)?;
+
+        let geneve = expect_kind!(found, LinkKind::Geneve);
+        assert_eq!(geneve.id, 234);
+        assert_eq!(geneve.remote, Some("10.0.0.2".parse()?));
+        assert_eq!((geneve.ttl, geneve.port), (64, 6081));
+        assert!(geneve.udp_csum);
+        Ok(())
+    }
+
+    #[test]
+    fn test_gre_nlas_round_trip() {
+        let gre = Gre {
+            remote: Some("10.0.0.2".parse().unwrap()),
+            ttl: 64,
+            flow_label: 0x12345,
+            tos: 0x10,
+            ikey: 100,
+            okey: 200,
+            encap: Some(TunnelEncap {
+                encap_type: TunnelEncapType::Fou,
+                sport: 5000,
+                dport: 5555,
+                ..Default::default()
+            }),
+            erspan_ver: Some(2),
+            erspan_dir: Some(1),
+            erspan_hwid: Some(7),
+            ..Default::default()
+        };
+        let bytes = utils::emit_nlas(&gre_nlas(&gre, true));
+        let found = gre_deserialize(&utils::parse_nlas(&bytes));
+        assert_eq!(found.remote, gre.remote);
+        assert_eq!(found.local, None);
+        assert_eq!(found.ttl, 64);
+        assert_eq!(found.tos, 0x10);
+        assert_eq!(found.flow_label, 0x12345);
+        assert_eq!((found.ikey, found.okey), (100, 200));
+        assert_eq!((found.iflags, found.oflags), (GRE_KEY, GRE_KEY));
+        assert_eq!(found.encap, gre.encap);
+        assert_eq!(found.erspan_ver, Some(2));
+        assert_eq!(found.erspan_dir, Some(1));
+        assert_eq!(found.erspan_hwid, Some(7));
+    }
+
+    #[test]
+    fn test_tunnel_encap_deserialize() {
+        let mut encap = TunnelEncap::default();
+        tunnel_encap_deserialize(&mut encap, &nla_u16(IFLA_GRE_ENCAP_TYPE, 7), IFLA_GRE_ENCAP_TYPE);
+        tunnel_encap_deserialize(&mut encap, &nla_u16(IFLA_GRE_ENCAP_DPORT, 5555u16.to_be()), IFLA_GRE_ENCAP_TYPE);
+        assert_eq!(encap.encap_type, TunnelEncapType::Other(7));
+        assert_eq!(encap.dport, 5555);
+    }
+
+    #[test]
+    fn test_add_link_tunnels() -> anyhow::Result<()> {
+        let links = [
+            ("gre234", LinkKind::Gre(Gre {
+                local: Some("10.0.0.1".parse()?),
+                remote: Some("10.0.0.2".parse()?),
+                ttl: 64,
+                ikey: 234,
+                okey: 234,
+                pmtudisc: Some(true),
+                ..Default::default()
+            })),
+            ("erspan234", LinkKind::Erspan(Gre {
+                local: Some("10.0.0.1".parse()?),
+                remote: Some("10.0.0.3".parse()?),
+                ikey: 1,
+                okey: 1,
+                oflags: GRE_SEQ,
+                erspan_ver: Some(1),
+                erspan_index: Some(123),
+                ..Default::default()
+            })),
+            ("ip6tnl234", LinkKind::Ip6tnl(Iptun {
+                local: Some("fd00::1".parse()?),
+                remote: Some("fd00::2".parse()?),
+                ttl: 32,
+                encap_limit: Some(4),
+                ..Default::default()
+            })),
+            ("ipip234", LinkKind::Ipip(Iptun {
+                remote: Some("10.0.0.4".parse()?),
+                encap: Some(TunnelEncap {
+                    encap_type: TunnelEncapType::Fou,
+                    dport: 5555,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })),
+        ];
+        for (name, link_kind) in links {
+            let link = Link {
+                link_attrs: LinkAttrs {
+                    name: name.to_string(),
+                    ..Default::default()
+                },
+                link_kind,
+            };
+            let found = add_fetch_del(&link)?;
+
+            assert_eq!(found.kind(), link.kind());
+            match (&link.link_kind, &found.link_kind) {
+                (LinkKind::Gre(want), LinkKind::Gre(got)) | (LinkKind::Erspan(want), LinkKind::Erspan(got)) => {
+                    assert_eq!(got.local, want.local);
+                    assert_eq!(got.remote, want.remote);
+                    assert_eq!(got.ikey, want.ikey);
+                    assert_eq!(got.okey, want.okey);
+                    assert_eq!(got.erspan_index, want.erspan_index);
+                }
+                (LinkKind::Ip6tnl(want), LinkKind::Ip6tnl(got)) | (LinkKind::Ipip(want), LinkKind::Ipip(got)) => {
+                    assert_eq!(got.local, want.local);
+                    assert_eq!(got.remote, want.remote);
+                    assert_eq!(got.encap, want.encap);
+                    if want.ttl > 0 {
+                        assert_eq!(got.ttl, want.ttl);
+                    }
+                }
+                _ => panic!("unexpected link kind: {:?}", found.link_kind),
+            }
+        }
+        Ok(())
+    }
+
     pub fn new_hardware_addr() -> anyhow::Result<MacAddr6> {
         let mut rng = rand::thread_rng();
         let mut hardware_addr = [0u8; 6];
@@ -1,25 +1,180 @@
 use std::fmt::Display;
 use std::net::IpAddr;
+use std::str::FromStr;
 
+use anyhow::{anyhow, bail};
+use bitflags::bitflags;
 use libc::{AF_INET, AF_INET6};
 use macaddr::MacAddr6;
-use netlink_packet_core::{NLM_F_CREATE, NLM_F_REPLACE};
-use netlink_packet_route::{IFA_F_PERMANENT, NDA_UNSPEC, NeighbourMessage, RtnlMessage};
+use netlink_packet_core::{NLM_F_ACK, NLM_F_APPEND, NLM_F_CREATE, NLM_F_DUMP, NLM_F_REPLACE};
+use netlink_packet_route::{NeighbourMessage, NTF_EXT_LEARNED, NTF_MASTER, NTF_OFFLOADED, NTF_PROXY, NTF_ROUTER, NTF_SELF, NTF_USE, NUD_DELAY, NUD_FAILED, NUD_INCOMPLETE, NUD_NOARP, NUD_PERMANENT, NUD_PROBE, NUD_REACHABLE, NUD_STALE, RTM_GETNEIGHTBL, RTM_NEWNEIGHTBL, RTM_SETNEIGHTBL, RtnlMessage};
 use netlink_packet_route::neighbour::Nla;
+use netlink_packet_route::neighbour_table::{self, ConfigBuffer, StatsBuffer};
+use netlink_packet_utils::nla::{DefaultNla, Nla as _, NlasIterator};
+use netlink_packet_utils::{Emitable, Parseable};
 
+use crate::{LinkIndex, utils};
 use crate::handle::NetlinkHandle;
+use crate::nl_linux::{NDA_FLAGS_EXT, NDA_PROTOCOL, NDTPA_ANYCAST_DELAY, NDTPA_APP_PROBES, NDTPA_BASE_REACHABLE_TIME, NDTPA_DELAY_PROBE_TIME, NDTPA_GC_STALETIME, NDTPA_IFINDEX, NDTPA_INTERVAL_PROBE_TIME_MS, NDTPA_LOCKTIME, NDTPA_MCAST_PROBES, NDTPA_MCAST_REPROBES, NDTPA_PROXY_DELAY, NDTPA_PROXY_QLEN, NDTPA_QUEUE_LEN, NDTPA_QUEUE_LENBYTES, NDTPA_REACHABLE_TIME, NDTPA_REFCNT, NDTPA_RETRANS_TIME, NDTPA_UCAST_PROBES, NTF_EXT_LOCKED, NTF_EXT_MANAGED, NTF_STICKY, rt_protocol};
+use crate::nl_type::{Family, FAMILY_ALL, FAMILY_V4, FAMILY_V6};
+use crate::rtnl::{FamilyHeader, RtnlExtMessage};
+
+bitflags! {
+    // NUD_* neighbour states
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    pub struct NudState: u16 {
+        const INCOMPLETE = NUD_INCOMPLETE;
+        const REACHABLE = NUD_REACHABLE;
+        const STALE = NUD_STALE;
+        const DELAY = NUD_DELAY;
+        const PROBE = NUD_PROBE;
+        const FAILED = NUD_FAILED;
+        const NOARP = NUD_NOARP;
+        const PERMANENT = NUD_PERMANENT;
+    }
+}
+
+bitflags! {
+    // NTF_* neighbour flags of ndm_flags
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    pub struct NeighFlags: u8 {
+        const USE = NTF_USE;
+        const SELF = NTF_SELF;
+        const MASTER = NTF_MASTER;
+        const PROXY = NTF_PROXY;
+        const EXT_LEARNED = NTF_EXT_LEARNED;
+        const OFFLOADED = NTF_OFFLOADED;
+        const STICKY = NTF_STICKY;
+        const ROUTER = NTF_ROUTER;
+    }
+}
+
+bitflags! {
+    // NTF_EXT_* neighbour flags of NDA_FLAGS_EXT
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    pub struct NeighFlagsExt: u32 {
+        const MANAGED = NTF_EXT_MANAGED;
+        const LOCKED = NTF_EXT_LOCKED;
+    }
+}
+
+// names as printed by `ip neigh`
+const NUD_STATE_NAMES: [(NudState, &str); 8] = [
+    (NudState::INCOMPLETE, "INCOMPLETE"),
+    (NudState::REACHABLE, "REACHABLE"),
+    (NudState::STALE, "STALE"),
+    (NudState::DELAY, "DELAY"),
+    (NudState::PROBE, "PROBE"),
+    (NudState::FAILED, "FAILED"),
+    (NudState::NOARP, "NOARP"),
+    (NudState::PERMANENT, "PERMANENT"),
+];
+
+const NEIGH_FLAG_NAMES: [(NeighFlags, &str); 8] = [
+    (NeighFlags::ROUTER, "router"),
+    (NeighFlags::PROXY, "proxy"),
+    (NeighFlags::EXT_LEARNED, "extern_learn"),
+    (NeighFlags::OFFLOADED, "offload"),
+    (NeighFlags::USE, "use"),
+    (NeighFlags::SELF, "self"),
+    (NeighFlags::MASTER, "master"),
+    (NeighFlags::STICKY, "sticky"),
+];
+
+const NEIGH_FLAG_EXT_NAMES: [(NeighFlagsExt, &str); 2] = [
+    (NeighFlagsExt::MANAGED, "managed"),
+    (NeighFlagsExt::LOCKED, "locked"),
+];
+
+fn fmt_names<T: bitflags::Flags + Copy>(f: &mut std::fmt::Formatter<'_>, value: T, names: &[(T, &str)]) -> std::fmt::Result {
+    let mut first = true;
+    for (flag, name) in names {
+        if value.contains(*flag) {
+            if !first {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", name)?;
+            first = false;
+        }
+    }
+    Ok(())
+}
+
+fn parse_names<T: bitflags::Flags + Copy>(s: &str, names: &[(T, &str)]) -> anyhow::Result<T> {
+    let mut value = T::empty();
+    for word in s.split_whitespace() {
+        let (flag, _) = names.iter()
+            .find(|(_, name)| name.eq_ignore_ascii_case(word))
+            .ok_or_else(|| anyhow!("invalid neigh flag or state: {}", word))?;
+        value.insert(*flag);
+    }
+    Ok(value)
+}
+
+impl Display for NudState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return write!(f, "NONE");
+        }
+        fmt_names(f, *self, &NUD_STATE_NAMES)
+    }
+}
+
+impl FromStr for NudState {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().eq_ignore_ascii_case("none") {
+            return Ok(NudState::empty());
+        }
+        parse_names(s, &NUD_STATE_NAMES)
+    }
+}
+
+impl Display for NeighFlags {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_names(f, *self, &NEIGH_FLAG_NAMES)
+    }
+}
+
+impl FromStr for NeighFlags {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_names(s, &NEIGH_FLAG_NAMES)
+    }
+}
+
+impl Display for NeighFlagsExt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_names(f, *self, &NEIGH_FLAG_EXT_NAMES)
+    }
+}
+
+impl FromStr for NeighFlagsExt {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_names(s, &NEIGH_FLAG_EXT_NAMES)
+    }
+}
 
 #[derive(Debug)]
 pub struct Neigh {
     pub link_index: u32,
     pub family: u8,
-    pub state: u16,
+    pub state: NudState,
     pub type_: u32,
-    pub flags: u32,
-    // pub flags_ext: u32,
+    pub flags: NeighFlags,
+    // sent as NDA_FLAGS_EXT
+    pub flags_ext: NeighFlagsExt,
     pub ip: IpAddr,
     pub hardware_addr: MacAddr6,
-    // pub llip_addr: Option<IpAddr>,
+    // IP link layer address, e.g. of a GRE tunnel
+    pub llip_addr: Option<IpAddr>,
+    // link layer address of non-Ethernet links, e.g. 20 byte IPoIB addresses
+    pub lladdr: Vec<u8>,
+    pub protocol: u8,
     pub vlan: u16,
     pub vni: u32,
     pub master_index: u32,
@@ -30,11 +185,15 @@ impl Default for Neigh {
         Self {
             link_index: 0,
             family: 0,
-            state: 0,
+            state: NudState::empty(),
             type_: 0,
-            flags: 0,
+            flags: NeighFlags::empty(),
+            flags_ext: NeighFlagsExt::empty(),
             ip: IpAddr::V4("0.0.0.0".parse().unwrap()),
             hardware_addr: MacAddr6::default(),
+            llip_addr: None,
+            lladdr: vec![],
+            protocol: 0,
             vlan: 0,
             vni: 0,
             master_index: 0,
@@ -42,9 +201,40 @@ impl Default for Neigh {
     }
 }
 
+impl Neigh {
+    // is_proxy reports whether this is a proxy arp/ndp entry, i.e. NTF_PROXY is set
+    pub fn is_proxy(&self) -> bool {
+        self.flags.contains(NeighFlags::PROXY)
+    }
+}
+
+// formats like a line of `ip neigh show`, links are printed as `if$index`
+// like iproute2 does for links it can not resolve
 impl Display for Neigh {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?} {:?}", self.ip, self.hardware_addr)
+        write!(f, "{} dev if{}", self.ip, self.link_index)?;
+        if let Some(llip_addr) = &self.llip_addr {
+            write!(f, " lladdr {}", llip_addr)?;
+        } else if !self.lladdr.is_empty() {
+            let lladdr = self.lladdr.iter().map(|b| format!("{:02x}", b)).collect::<Vec<_>>();
+            write!(f, " lladdr {}", lladdr.join(":"))?;
+        } else if !self.hardware_addr.is_nil() {
+            write!(f, " lladdr {}", self.hardware_addr.to_string().to_lowercase())?;
+        }
+        let flags = self.flags & (NeighFlags::ROUTER | NeighFlags::PROXY | NeighFlags::EXT_LEARNED | NeighFlags::OFFLOADED);
+        if !flags.is_empty() {
+            write!(f, " {}", flags)?;
+        }
+        if !self.flags_ext.is_empty() {
+            write!(f, " {}", self.flags_ext)?;
+        }
+        if self.protocol > 0 {
+            write!(f, " proto {}", rt_protocol(self.protocol))?;
+        }
+        if !self.is_proxy() {
+            write!(f, " {}", self.state)?;
+        }
+        Ok(())
     }
 }
 
@@ -52,31 +242,126 @@ pub fn neigh_set(neigh: &Neigh) -> anyhow::Result<()> {
     neigh_add(neigh, NLM_F_CREATE | NLM_F_REPLACE)
 }
 
-fn new_neigh(neigh: &Neigh, dst: IpAddr) -> NeighbourMessage {
-    let mut req = NeighbourMessage::default();
-    req.header.family = if dst.is_ipv4() {
-        AF_INET as u8
-    } else {
-        AF_INET6 as u8
+// neigh_append adds a neighbour entry, appending to an existing one instead of replacing it.
+// Equivalent to: `bridge fdb append`, `ip neigh add` for non-fdb entries
+pub fn neigh_append(neigh: &Neigh) -> anyhow::Result<()> {
+    neigh_add(neigh, NLM_F_CREATE | NLM_F_APPEND)
+}
+
+pub fn neigh_add(neigh: &Neigh, flags: u16) -> anyhow::Result<()> {
+    let req = neigh_msg(neigh);
+    let _ = NetlinkHandle::new().execute(RtnlMessage::NewNeighbour(req), flags | NLM_F_ACK)?;
+    Ok(())
+}
+
+// neigh_del deletes a neighbour entry.
+// Equivalent to: `ip neigh del $ip dev $link`
+pub fn neigh_del(neigh: &Neigh) -> anyhow::Result<()> {
+    let req = neigh_msg(neigh);
+    let _ = NetlinkHandle::new().execute(RtnlMessage::DelNeighbour(req), NLM_F_ACK)?;
+    Ok(())
+}
+
+// neigh_get returns the neighbour entry of `ip` on a link, None if there is none.
+// Equivalent to: `ip neigh get $ip dev $link`
+pub fn neigh_get(link_index: LinkIndex, ip: IpAddr) -> anyhow::Result<Option<Neigh>> {
+    let mut msg = NeighbourMessage::default();
+    msg.header.family = if ip.is_ipv4() { FAMILY_V4 } else { FAMILY_V6 };
+    msg.header.ifindex = link_index;
+    msg.nlas.push(Nla::Destination(utils::ip_to_bytes(&ip)));
+
+    let resp = match NetlinkHandle::new().execute(RtnlMessage::GetNeighbour(msg), 0) {
+        Ok(resp) => resp,
+        Err(e) if is_not_found(&e) => return Ok(None),
+        Err(e) => return Err(e),
     };
-    req.header.ifindex = neigh.link_index;
-    req.header.state = IFA_F_PERMANENT as u16;
-    req.header.ntype = NDA_UNSPEC as u8;
-    req.nlas.push(Nla::Destination(match dst {
-        IpAddr::V4(v4) => v4.octets().to_vec(),
-        IpAddr::V6(v6) => v6.octets().to_vec(),
-    }));
+    for msg in &resp {
+        if let RtnlMessage::NewNeighbour(neigh) = msg {
+            return Ok(Some(Neigh::try_from(neigh)?));
+        }
+    }
+    Ok(None)
+}
 
-    req
+// neigh_probe moves an existing neighbour entry to NUD_PROBE, so the kernel re-resolves it
+// keeping the current link layer address until it gets an answer.
+// Equivalent to: `ip neigh change $ip dev $link nud probe`
+pub fn neigh_probe(link_index: LinkIndex, ip: IpAddr) -> anyhow::Result<()> {
+    let mut neigh = match neigh_get(link_index, ip)? {
+        Some(neigh) => neigh,
+        None => bail!("neigh {} not found on link {}", ip, link_index),
+    };
+    neigh.state = NudState::PROBE;
+    neigh.flags = NeighFlags::empty();
+    neigh_add(&neigh, NLM_F_REPLACE)
 }
 
-pub fn neigh_add(neigh: &Neigh, flags: u16) -> anyhow::Result<()> {
+// neigh_flush deletes the neighbour entries of a link in any of `state`, all links if `link_index`
+// is 0. Like iproute2, an empty `state` flushes everything but permanent and noarp entries.
+// Equivalent to: `ip neigh flush dev $link nud stale`
+pub fn neigh_flush(link_index: LinkIndex, family: Family, state: NudState) -> anyhow::Result<()> {
+    let state = if state.is_empty() { !(NudState::PERMANENT | NudState::NOARP) } else { state };
+    for neigh in neigh_list(link_index, family)? {
+        if !neigh.state.intersects(state) {
+            continue;
+        }
+        match neigh_del(&neigh) {
+            // the entry may have been garbage collected in the meantime
+            Err(e) if is_not_found(&e) => {}
+            res => res?,
+        }
+    }
+    Ok(())
+}
+
+// the kernel reports a missing neighbour entry as ENOENT and a missing link as ENODEV
+fn is_not_found(e: &anyhow::Error) -> bool {
+    e.downcast_ref::<std::io::Error>().is_some_and(|it| {
+        it.raw_os_error() == Some(libc::ENOENT) || it.kind() == std::io::ErrorKind::NotFound
+    })
+}
+
+// neigh_list lists the neighbour entries of a link, all links if `link_index` is 0.
+// Equivalent to: `ip neigh show dev $link`
+pub fn neigh_list(link_index: LinkIndex, family: Family) -> anyhow::Result<Vec<Neigh>> {
+    neigh_list_execute(link_index, family, NeighFlags::empty())
+}
+
+// neigh_proxy_list lists the proxy arp/ndp entries of a link, all links if `link_index` is 0.
+// Proxy entries are added and deleted by neigh_add/neigh_del with NTF_PROXY in `flags`.
+// Equivalent to: `ip neigh show proxy dev $link`
+pub fn neigh_proxy_list(link_index: LinkIndex, family: Family) -> anyhow::Result<Vec<Neigh>> {
+    neigh_list_execute(link_index, family, NeighFlags::PROXY)
+}
+
+fn neigh_list_execute(link_index: LinkIndex, family: Family, flags: NeighFlags) -> anyhow::Result<Vec<Neigh>> {
+    let mut msg = NeighbourMessage::default();
+    msg.header.family = family;
+    msg.header.flags = flags.bits();
+
+    let resp = NetlinkHandle::new().execute(RtnlMessage::GetNeighbour(msg), NLM_F_DUMP | NLM_F_ACK)?;
+    let mut result = Vec::new();
+    for msg in &resp {
+        if let RtnlMessage::NewNeighbour(neigh) = msg {
+            if link_index != 0 && neigh.header.ifindex != link_index {
+                continue;
+            }
+            if family != FAMILY_ALL && neigh.header.family != family {
+                continue;
+            }
+            result.push(Neigh::try_from(neigh)?);
+        }
+    }
+    Ok(result)
+}
+
+fn neigh_msg(neigh: &Neigh) -> NeighbourMessage {
     let mut req = NeighbourMessage::default();
 
     req.header.ifindex = neigh.link_index;
-    req.header.state = neigh.state;
+    req.header.state = neigh.state.bits();
     req.header.ntype = neigh.type_ as u8;
-    req.header.flags = neigh.flags as u8;
+    req.header.flags = neigh.flags.bits();
 
     if neigh.family > 0 {
         req.header.family = neigh.family;
@@ -93,9 +378,19 @@ pub fn neigh_add(neigh: &Neigh, flags: u16) -> anyhow::Result<()> {
         IpAddr::V6(v6) => v6.octets().to_vec(),
     }));
 
-    if !neigh.hardware_addr.is_nil() {
+    if let Some(llip_addr) = &neigh.llip_addr {
+        req.nlas.push(Nla::LinkLocalAddress(utils::ip_to_bytes(llip_addr)));
+    } else if !neigh.lladdr.is_empty() {
+        req.nlas.push(Nla::LinkLocalAddress(neigh.lladdr.clone()));
+    } else if !neigh.hardware_addr.is_nil() {
         req.nlas.push(Nla::LinkLocalAddress(neigh.hardware_addr.into_array().to_vec()));
     }
+    if !neigh.flags_ext.is_empty() {
+        req.nlas.push(Nla::Other(DefaultNla::new(NDA_FLAGS_EXT, neigh.flags_ext.bits().to_ne_bytes().to_vec())));
+    }
+    if neigh.protocol > 0 {
+        req.nlas.push(Nla::Other(DefaultNla::new(NDA_PROTOCOL, vec![neigh.protocol])));
+    }
 
     if neigh.vlan > 0 {
         req.nlas.push(Nla::Vlan(neigh.vlan));
@@ -106,17 +401,268 @@ pub fn neigh_add(neigh: &Neigh, flags: u16) -> anyhow::Result<()> {
     if neigh.master_index > 0 {
         req.nlas.push(Nla::Master(u32::to_ne_bytes(neigh.master_index).to_vec()));
     }
-    for nla in &req.nlas {
-        println!("nla: {:?}", nla)
+    req
+}
+
+impl TryFrom<&NeighbourMessage> for Neigh {
+    type Error = anyhow::Error;
+
+    fn try_from(msg: &NeighbourMessage) -> Result<Self, Self::Error> {
+        let mut neigh = Neigh {
+            link_index: msg.header.ifindex,
+            family: msg.header.family,
+            state: NudState::from_bits_retain(msg.header.state),
+            type_: msg.header.ntype as u32,
+            flags: NeighFlags::from_bits_retain(msg.header.flags),
+            ..Default::default()
+        };
+        for nla in &msg.nlas {
+            match nla {
+                Nla::Destination(bytes) => {
+                    neigh.ip = match neigh.family {
+                        FAMILY_V4 | FAMILY_V6 => utils::bytes_to_ip(bytes, neigh.family)?,
+                        _ => utils::bytes_to_ip_by_len(bytes)?,
+                    };
+                }
+                Nla::LinkLocalAddress(bytes) => match bytes.len() {
+                    6 => {
+                        neigh.hardware_addr = MacAddr6::new(bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5]);
+                    }
+                    4 | 16 => {
+                        neigh.llip_addr = Some(utils::bytes_to_ip_by_len(bytes)?);
+                    }
+                    _ => {
+                        neigh.lladdr = bytes.clone();
+                    }
+                },
+                Nla::Vlan(vlan) => {
+                    neigh.vlan = *vlan;
+                }
+                Nla::Vni(vni) => {
+                    neigh.vni = *vni;
+                }
+                Nla::Master(bytes) if bytes.len() == 4 => {
+                    neigh.master_index = u32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+                }
+                Nla::Other(nla) => {
+                    let bytes = utils::default_nla_value(nla);
+                    match nla.kind() {
+                        NDA_FLAGS_EXT if bytes.len() == 4 => {
+                            neigh.flags_ext = NeighFlagsExt::from_bits_retain(u32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]));
+                        }
+                        NDA_PROTOCOL if bytes.len() == 1 => {
+                            neigh.protocol = bytes[0];
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(neigh)
+    }
+}
+
+pub type NtableConfig = neighbour_table::Config;
+pub type NtableStats = neighbour_table::Stats;
+
+// NtableParms are the NDTA_PARMS of a neighbour table, the defaults if `ifindex` is 0.
+// Times are in milliseconds, `refcnt` and `reachable_time` are read only.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NtableParms {
+    pub ifindex: u32,
+    pub refcnt: Option<u32>,
+    pub reachable_time: Option<u64>,
+    pub base_reachable_time: Option<u64>,
+    pub retrans_time: Option<u64>,
+    pub gc_staletime: Option<u64>,
+    pub delay_probe_time: Option<u64>,
+    pub queue_len: Option<u32>,
+    pub app_probes: Option<u32>,
+    pub ucast_probes: Option<u32>,
+    pub mcast_probes: Option<u32>,
+    pub anycast_delay: Option<u64>,
+    pub proxy_delay: Option<u64>,
+    pub proxy_qlen: Option<u32>,
+    pub locktime: Option<u64>,
+    pub queue_lenbytes: Option<u32>,
+    pub mcast_reprobes: Option<u32>,
+    pub interval_probe_time_ms: Option<u64>,
+}
+
+// Ntable is a neighbour table (`arp_cache`, `ndisc_cache`), or the per device parms of one.
+// Only `name`, the thresholds, `gc_interval` and `parms` are used by ntable_change.
+#[derive(Debug, Clone, Default)]
+pub struct Ntable {
+    pub family: Family,
+    pub name: String,
+    pub gc_thresh1: Option<u32>,
+    pub gc_thresh2: Option<u32>,
+    pub gc_thresh3: Option<u32>,
+    // milliseconds
+    pub gc_interval: Option<u64>,
+    pub config: Option<NtableConfig>,
+    pub stats: Option<NtableStats>,
+    pub parms: Option<NtableParms>,
+}
+
+// ntable_list lists the neighbour tables of a family and their per device parms.
+// Equivalent to: `ip ntable show`
+pub fn ntable_list(family: Family) -> anyhow::Result<Vec<Ntable>> {
+    let msg = NtableMessage {
+        message_type: RTM_GETNEIGHTBL,
+        header: FamilyHeader { family },
+        nlas: vec![],
+    };
+    let resp = NetlinkHandle::new().execute(msg, NLM_F_DUMP | NLM_F_ACK)?;
+    let mut result = Vec::new();
+    for msg in &resp {
+        if msg.message_type == RTM_NEWNEIGHTBL {
+            result.push(Ntable::try_from(msg)?);
+        }
     }
+    Ok(result)
+}
 
-    let _ = NetlinkHandle::new().execute(RtnlMessage::NewNeighbour(req), flags)?;
+// ntable_change changes the parameters of a neighbour table, `name` is required.
+// Equivalent to: `ip ntable change name $name [dev $link] ...`
+pub fn ntable_change(ntable: &Ntable) -> anyhow::Result<()> {
+    if ntable.name.is_empty() {
+        bail!("ntable name is required");
+    }
+    let mut msg = NtableMessage {
+        message_type: RTM_SETNEIGHTBL,
+        header: FamilyHeader { family: ntable.family },
+        nlas: vec![neighbour_table::Nla::Name(ntable.name.clone())],
+    };
+    if let Some(v) = ntable.gc_thresh1 {
+        msg.nlas.push(neighbour_table::Nla::Threshold1(v));
+    }
+    if let Some(v) = ntable.gc_thresh2 {
+        msg.nlas.push(neighbour_table::Nla::Threshold2(v));
+    }
+    if let Some(v) = ntable.gc_thresh3 {
+        msg.nlas.push(neighbour_table::Nla::Threshold3(v));
+    }
+    if let Some(v) = ntable.gc_interval {
+        msg.nlas.push(neighbour_table::Nla::GcInterval(v));
+    }
+    if let Some(parms) = &ntable.parms {
+        let nlas = ntable_parms_nlas(parms);
+        let mut bytes = vec![0u8; nlas.as_slice().buffer_len()];
+        nlas.as_slice().emit(&mut bytes);
+        msg.nlas.push(neighbour_table::Nla::Parms(bytes));
+    }
+    let _ = NetlinkHandle::new().execute(msg, NLM_F_ACK)?;
     Ok(())
 }
 
+fn ntable_parms_nlas(parms: &NtableParms) -> Vec<DefaultNla> {
+    let mut nlas = vec![DefaultNla::new(NDTPA_IFINDEX, parms.ifindex.to_ne_bytes().to_vec())];
+    let u32_parms = [
+        (NDTPA_QUEUE_LEN, parms.queue_len),
+        (NDTPA_APP_PROBES, parms.app_probes),
+        (NDTPA_UCAST_PROBES, parms.ucast_probes),
+        (NDTPA_MCAST_PROBES, parms.mcast_probes),
+        (NDTPA_PROXY_QLEN, parms.proxy_qlen),
+        (NDTPA_QUEUE_LENBYTES, parms.queue_lenbytes),
+        (NDTPA_MCAST_REPROBES, parms.mcast_reprobes),
+    ];
+    for (kind, value) in u32_parms {
+        if let Some(v) = value {
+            nlas.push(DefaultNla::new(kind, v.to_ne_bytes().to_vec()));
+        }
+    }
+    let u64_parms = [
+        (NDTPA_BASE_REACHABLE_TIME, parms.base_reachable_time),
+        (NDTPA_RETRANS_TIME, parms.retrans_time),
+        (NDTPA_GC_STALETIME, parms.gc_staletime),
+        (NDTPA_DELAY_PROBE_TIME, parms.delay_probe_time),
+        (NDTPA_ANYCAST_DELAY, parms.anycast_delay),
+        (NDTPA_PROXY_DELAY, parms.proxy_delay),
+        (NDTPA_LOCKTIME, parms.locktime),
+        (NDTPA_INTERVAL_PROBE_TIME_MS, parms.interval_probe_time_ms),
+    ];
+    for (kind, value) in u64_parms {
+        if let Some(v) = value {
+            nlas.push(DefaultNla::new(kind, v.to_ne_bytes().to_vec()));
+        }
+    }
+    nlas
+}
+
+fn ntable_parms_deserialize(bytes: &[u8]) -> anyhow::Result<NtableParms> {
+    let mut parms = NtableParms::default();
+    for nla in NlasIterator::new(bytes) {
+        let nla = nla?;
+        let value = nla.value();
+        let as_u32 = || <[u8; 4]>::try_from(value).ok().map(u32::from_ne_bytes);
+        let as_u64 = || <[u8; 8]>::try_from(value).ok().map(u64::from_ne_bytes);
+        match nla.kind() {
+            NDTPA_IFINDEX => parms.ifindex = as_u32().unwrap_or_default(),
+            NDTPA_REFCNT => parms.refcnt = as_u32(),
+            NDTPA_REACHABLE_TIME => parms.reachable_time = as_u64(),
+            NDTPA_BASE_REACHABLE_TIME => parms.base_reachable_time = as_u64(),
+            NDTPA_RETRANS_TIME => parms.retrans_time = as_u64(),
+            NDTPA_GC_STALETIME => parms.gc_staletime = as_u64(),
+            NDTPA_DELAY_PROBE_TIME => parms.delay_probe_time = as_u64(),
+            NDTPA_QUEUE_LEN => parms.queue_len = as_u32(),
+            NDTPA_APP_PROBES => parms.app_probes = as_u32(),
+            NDTPA_UCAST_PROBES => parms.ucast_probes = as_u32(),
+            NDTPA_MCAST_PROBES => parms.mcast_probes = as_u32(),
+            NDTPA_ANYCAST_DELAY => parms.anycast_delay = as_u64(),
+            NDTPA_PROXY_DELAY => parms.proxy_delay = as_u64(),
+            NDTPA_PROXY_QLEN => parms.proxy_qlen = as_u32(),
+            NDTPA_LOCKTIME => parms.locktime = as_u64(),
+            NDTPA_QUEUE_LENBYTES => parms.queue_lenbytes = as_u32(),
+            NDTPA_MCAST_REPROBES => parms.mcast_reprobes = as_u32(),
+            NDTPA_INTERVAL_PROBE_TIME_MS => parms.interval_probe_time_ms = as_u64(),
+            _ => {}
+        }
+    }
+    Ok(parms)
+}
+
+impl TryFrom<&NtableMessage> for Ntable {
+    type Error = anyhow::Error;
+
+    fn try_from(msg: &NtableMessage) -> Result<Self, Self::Error> {
+        let mut ntable = Ntable {
+            family: msg.header.family,
+            ..Default::default()
+        };
+        for nla in &msg.nlas {
+            match nla {
+                neighbour_table::Nla::Name(name) => ntable.name = name.clone(),
+                neighbour_table::Nla::Threshold1(v) => ntable.gc_thresh1 = Some(*v),
+                neighbour_table::Nla::Threshold2(v) => ntable.gc_thresh2 = Some(*v),
+                neighbour_table::Nla::Threshold3(v) => ntable.gc_thresh3 = Some(*v),
+                neighbour_table::Nla::GcInterval(v) => ntable.gc_interval = Some(*v),
+                neighbour_table::Nla::Config(bytes) => {
+                    ntable.config = Some(NtableConfig::parse(&ConfigBuffer::new_checked(bytes.as_slice())?)?);
+                }
+                neighbour_table::Nla::Stats(bytes) => {
+                    ntable.stats = Some(NtableStats::parse(&StatsBuffer::new_checked(bytes.as_slice())?)?);
+                }
+                neighbour_table::Nla::Parms(bytes) => {
+                    ntable.parms = Some(ntable_parms_deserialize(bytes)?);
+                }
+                _ => {}
+            }
+        }
+        Ok(ntable)
+    }
+}
+
+// NtableMessage is a RTM_NEWNEIGHTBL/RTM_GETNEIGHTBL/RTM_SETNEIGHTBL message
+pub type NtableMessage = RtnlExtMessage<FamilyHeader, neighbour_table::Nla>;
+
 #[cfg(test)]
 mod tests {
-    use netlink_packet_route::NUD_PERMANENT;
+    use netlink_packet_core::NLM_F_EXCL;
+    use netlink_packet_route::RTPROT_STATIC;
+
+    use crate::TryAsLinkIndex;
 
     use super::*;
 
@@ -124,11 +670,183 @@ mod tests {
     fn test_neigh_set() {
         let neigh = Neigh {
             link_index: 5,
-            state: NUD_PERMANENT,
+            state: NudState::PERMANENT,
             ip: "10.0.0.4".parse().unwrap(),
             hardware_addr: "3a:91:c1:3f:ee:54".parse().unwrap(),
             ..Default::default()
         };
         let _ = neigh_set(&neigh);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_neigh_add_ext_flags() -> anyhow::Result<()> {
+        let link_id = "eth0".try_as_index()?.unwrap();
+        // the kernel rejects NTF_EXT_LEARNED together with NTF_MANAGED
+        let learned = Neigh {
+            link_index: link_id,
+            state: NudState::REACHABLE,
+            flags: NeighFlags::EXT_LEARNED,
+            protocol: RTPROT_STATIC,
+            ip: "10.0.0.5".parse().unwrap(),
+            hardware_addr: "3a:91:c1:3f:ee:55".parse().unwrap(),
+            ..Default::default()
+        };
+        let managed = Neigh {
+            link_index: link_id,
+            state: NudState::REACHABLE,
+            flags_ext: NeighFlagsExt::MANAGED,
+            ip: "10.0.0.6".parse().unwrap(),
+            hardware_addr: "3a:91:c1:3f:ee:56".parse().unwrap(),
+            ..Default::default()
+        };
+        neigh_append(&learned)?;
+        let result = neigh_append(&managed);
+        let list = neigh_list(link_id, FAMILY_V4)?;
+        neigh_del(&learned)?;
+        result?;
+        neigh_del(&managed)?;
+
+        let found = list.iter().find(|it| it.ip == learned.ip).unwrap();
+        assert!(found.flags.contains(NeighFlags::EXT_LEARNED));
+        assert!(!found.flags_ext.contains(NeighFlagsExt::MANAGED));
+        assert_eq!(found.protocol, RTPROT_STATIC);
+        let found = list.iter().find(|it| it.ip == managed.ip).unwrap();
+        assert!(found.flags_ext.contains(NeighFlagsExt::MANAGED));
+        assert!(!found.flags.contains(NeighFlags::EXT_LEARNED));
+        Ok(())
+    }
+
+    #[test]
+    fn test_neigh_display() -> anyhow::Result<()> {
+        assert_eq!(NudState::empty().to_string(), "NONE");
+        assert_eq!((NudState::STALE | NudState::NOARP).to_string(), "STALE NOARP");
+        assert_eq!("reachable".parse::<NudState>()?, NudState::REACHABLE);
+        assert_eq!("none".parse::<NudState>()?, NudState::empty());
+        assert!("bogus".parse::<NudState>().is_err());
+        assert_eq!("router extern_learn".parse::<NeighFlags>()?, NeighFlags::ROUTER | NeighFlags::EXT_LEARNED);
+        assert_eq!(NeighFlagsExt::MANAGED.to_string(), "managed");
+
+        let neigh = Neigh {
+            link_index: 2,
+            state: NudState::REACHABLE,
+            flags: NeighFlags::ROUTER,
+            protocol: RTPROT_STATIC,
+            ip: "10.0.0.4".parse()?,
+            hardware_addr: "3a:91:c1:3f:ee:54".parse()?,
+            ..Default::default()
+        };
+        assert_eq!(neigh.to_string(), "10.0.0.4 dev if2 lladdr 3a:91:c1:3f:ee:54 router proto static REACHABLE");
+        assert_eq!(Neigh { protocol: 200, ..neigh }.to_string(), "10.0.0.4 dev if2 lladdr 3a:91:c1:3f:ee:54 router proto 200 REACHABLE");
+        Ok(())
+    }
+
+    #[test]
+    fn test_neigh_proxy() -> anyhow::Result<()> {
+        let link_id = "eth0".try_as_index()?.unwrap();
+        let neigh = Neigh {
+            link_index: link_id,
+            flags: NeighFlags::PROXY,
+            ip: "10.0.0.6".parse().unwrap(),
+            ..Default::default()
+        };
+        neigh_add(&neigh, NLM_F_CREATE | NLM_F_EXCL)?;
+        let list = neigh_proxy_list(link_id, FAMILY_V4)?;
+        let found = list.iter().find(|it| it.ip == neigh.ip).unwrap();
+        assert!(found.is_proxy());
+        assert!(neigh_list(link_id, FAMILY_V4)?.iter().all(|it| it.ip != neigh.ip));
+
+        neigh_del(&neigh)?;
+        let list = neigh_proxy_list(link_id, FAMILY_V4)?;
+        assert!(list.iter().all(|it| it.ip != neigh.ip));
+        Ok(())
+    }
+
+    #[test]
+    fn test_ntable_list() -> anyhow::Result<()> {
+        let tables = ntable_list(FAMILY_V4)?;
+        let arp = tables.iter().find(|it| it.name == "arp_cache" && it.gc_thresh1.is_some()).unwrap();
+        assert!(arp.stats.is_some());
+        assert_eq!(arp.parms.as_ref().unwrap().ifindex, 0);
+        let link_id = "eth0".try_as_index()?.unwrap();
+        assert!(tables.iter().any(|it| it.parms.as_ref().is_some_and(|parms| parms.ifindex == link_id)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_ntable_change() -> anyhow::Result<()> {
+        let link_id = "eth0".try_as_index()?.unwrap();
+        ntable_change(&Ntable {
+            family: FAMILY_V4,
+            name: "arp_cache".to_string(),
+            gc_thresh3: Some(16384),
+            ..Default::default()
+        })?;
+        ntable_change(&Ntable {
+            family: FAMILY_V4,
+            name: "arp_cache".to_string(),
+            parms: Some(NtableParms {
+                ifindex: link_id,
+                base_reachable_time: Some(60000),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })?;
+        let tables = ntable_list(FAMILY_V4)?;
+        let arp = tables.iter().find(|it| it.name == "arp_cache" && it.gc_thresh3.is_some()).unwrap();
+        assert_eq!(arp.gc_thresh3, Some(16384));
+        let dev = tables.iter()
+            .filter_map(|it| it.parms.as_ref())
+            .find(|parms| parms.ifindex == link_id)
+            .unwrap();
+        assert_eq!(dev.base_reachable_time, Some(60000));
+        Ok(())
+    }
+
+    #[test]
+    fn test_neigh_list() -> anyhow::Result<()> {
+        let link_id = "eth0".try_as_index()?.unwrap();
+        let neigh = Neigh {
+            link_index: link_id,
+            state: NudState::PERMANENT,
+            ip: "10.0.0.4".parse().unwrap(),
+            hardware_addr: "3a:91:c1:3f:ee:54".parse().unwrap(),
+            ..Default::default()
+        };
+        neigh_set(&neigh)?;
+        let list = neigh_list(link_id, FAMILY_V4)?;
+        let found = list.iter().find(|it| it.ip == neigh.ip).unwrap();
+        assert_eq!(found.hardware_addr, neigh.hardware_addr);
+        assert_eq!(found.state, NudState::PERMANENT);
+
+        neigh_del(&neigh)?;
+        let list = neigh_list(link_id, FAMILY_V4)?;
+        assert!(list.iter().all(|it| it.ip != neigh.ip));
+        Ok(())
+    }
+
+    #[test]
+    fn test_neigh_get_probe_flush() -> anyhow::Result<()> {
+        let link_id = "eth0".try_as_index()?.unwrap();
+        let neigh = Neigh {
+            link_index: link_id,
+            state: NudState::STALE,
+            ip: "10.0.0.7".parse().unwrap(),
+            hardware_addr: "3a:91:c1:3f:ee:57".parse().unwrap(),
+            ..Default::default()
+        };
+        neigh_set(&neigh)?;
+        let found = neigh_get(link_id, neigh.ip)?.unwrap();
+        assert_eq!(found.hardware_addr, neigh.hardware_addr);
+        assert!(neigh_get(link_id, "10.0.0.8".parse()?)?.is_none());
+
+        neigh_probe(link_id, neigh.ip)?;
+        let found = neigh_get(link_id, neigh.ip)?.unwrap();
+        assert_eq!(found.hardware_addr, neigh.hardware_addr);
+        assert_ne!(found.state, NudState::STALE);
+
+        neigh_set(&neigh)?;
+        neigh_flush(link_id, FAMILY_V4, NudState::STALE)?;
+        assert!(neigh_get(link_id, neigh.ip)?.is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,228 @@
+use anyhow::{anyhow, Context};
+use netlink_packet_core::{NLM_F_ACK, NLM_F_DUMP};
+use netlink_packet_route::{RTM_DELNETCONF, RTM_GETNETCONF, RTM_NEWNETCONF};
+use netlink_packet_utils::nla::{self, DefaultNla, NlaBuffer};
+use netlink_packet_utils::parsers::parse_i32;
+use netlink_packet_utils::{DecodeError, Parseable};
+
+use crate::handle::NetlinkHandle;
+use crate::nl_type::Family;
+use crate::rtnl::{FamilyHeader, RtnlExtMessage};
+
+pub const NETCONFA_UNSPEC: u16 = 0;
+pub const NETCONFA_IFINDEX: u16 = 1;
+pub const NETCONFA_FORWARDING: u16 = 2;
+pub const NETCONFA_RP_FILTER: u16 = 3;
+pub const NETCONFA_MC_FORWARDING: u16 = 4;
+pub const NETCONFA_PROXY_NEIGH: u16 = 5;
+pub const NETCONFA_IGNORE_ROUTES_WITH_LINKDOWN: u16 = 6;
+pub const NETCONFA_INPUT: u16 = 7;
+pub const NETCONFA_BC_FORWARDING: u16 = 8;
+
+// special ifindex values for the `all` and `default` devconf
+pub const NETCONFA_IFINDEX_ALL: i32 = -1;
+pub const NETCONFA_IFINDEX_DEFAULT: i32 = -2;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum NetconfNla {
+    Unspec(Vec<u8>),
+    IfIndex(i32),
+    Forwarding(i32),
+    RpFilter(i32),
+    McForwarding(i32),
+    ProxyNeigh(i32),
+    IgnoreRoutesWithLinkdown(i32),
+    Input(i32),
+    BcForwarding(i32),
+    Other(DefaultNla),
+}
+
+impl nla::Nla for NetconfNla {
+    fn value_len(&self) -> usize {
+        match self {
+            NetconfNla::Unspec(bytes) => bytes.len(),
+            NetconfNla::Other(attr) => attr.value_len(),
+            _ => 4,
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            NetconfNla::Unspec(_) => NETCONFA_UNSPEC,
+            NetconfNla::IfIndex(_) => NETCONFA_IFINDEX,
+            NetconfNla::Forwarding(_) => NETCONFA_FORWARDING,
+            NetconfNla::RpFilter(_) => NETCONFA_RP_FILTER,
+            NetconfNla::McForwarding(_) => NETCONFA_MC_FORWARDING,
+            NetconfNla::ProxyNeigh(_) => NETCONFA_PROXY_NEIGH,
+            NetconfNla::IgnoreRoutesWithLinkdown(_) => NETCONFA_IGNORE_ROUTES_WITH_LINKDOWN,
+            NetconfNla::Input(_) => NETCONFA_INPUT,
+            NetconfNla::BcForwarding(_) => NETCONFA_BC_FORWARDING,
+            NetconfNla::Other(attr) => attr.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            NetconfNla::Unspec(bytes) => buffer.copy_from_slice(bytes),
+            NetconfNla::IfIndex(v)
+            | NetconfNla::Forwarding(v)
+            | NetconfNla::RpFilter(v)
+            | NetconfNla::McForwarding(v)
+            | NetconfNla::ProxyNeigh(v)
+            | NetconfNla::IgnoreRoutesWithLinkdown(v)
+            | NetconfNla::Input(v)
+            | NetconfNla::BcForwarding(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            NetconfNla::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for NetconfNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            NETCONFA_UNSPEC => NetconfNla::Unspec(payload.to_vec()),
+            NETCONFA_IFINDEX => NetconfNla::IfIndex(parse_i32(payload).context("invalid NETCONFA_IFINDEX value")?),
+            NETCONFA_FORWARDING => NetconfNla::Forwarding(parse_i32(payload).context("invalid NETCONFA_FORWARDING value")?),
+            NETCONFA_RP_FILTER => NetconfNla::RpFilter(parse_i32(payload).context("invalid NETCONFA_RP_FILTER value")?),
+            NETCONFA_MC_FORWARDING => NetconfNla::McForwarding(parse_i32(payload).context("invalid NETCONFA_MC_FORWARDING value")?),
+            NETCONFA_PROXY_NEIGH => NetconfNla::ProxyNeigh(parse_i32(payload).context("invalid NETCONFA_PROXY_NEIGH value")?),
+            NETCONFA_IGNORE_ROUTES_WITH_LINKDOWN => NetconfNla::IgnoreRoutesWithLinkdown(
+                parse_i32(payload).context("invalid NETCONFA_IGNORE_ROUTES_WITH_LINKDOWN value")?,
+            ),
+            NETCONFA_INPUT => NetconfNla::Input(parse_i32(payload).context("invalid NETCONFA_INPUT value")?),
+            NETCONFA_BC_FORWARDING => NetconfNla::BcForwarding(parse_i32(payload).context("invalid NETCONFA_BC_FORWARDING value")?),
+            _ => NetconfNla::Other(DefaultNla::parse(buf).context("invalid NETCONFA nla")?),
+        })
+    }
+}
+
+// NetconfMessage is a RTM_NEWNETCONF/RTM_DELNETCONF/RTM_GETNETCONF message
+pub type NetconfMessage = RtnlExtMessage<FamilyHeader, NetconfNla>;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Netconf {
+    pub family: Family,
+    // link index, or NETCONFA_IFINDEX_ALL / NETCONFA_IFINDEX_DEFAULT
+    pub ifindex: i32,
+    pub forwarding: Option<i32>,
+    pub rp_filter: Option<i32>,
+    pub mc_forwarding: Option<i32>,
+    pub proxy_neigh: Option<i32>,
+    pub ignore_routes_with_linkdown: Option<i32>,
+    pub input: Option<i32>,
+    pub bc_forwarding: Option<i32>,
+}
+
+impl Netconf {
+    fn from_nlas(family: Family, nlas: &[NetconfNla]) -> Self {
+        let mut netconf = Netconf {
+            family,
+            ..Default::default()
+        };
+        for nla in nlas {
+            match nla {
+                NetconfNla::IfIndex(v) => netconf.ifindex = *v,
+                NetconfNla::Forwarding(v) => netconf.forwarding = Some(*v),
+                NetconfNla::RpFilter(v) => netconf.rp_filter = Some(*v),
+                NetconfNla::McForwarding(v) => netconf.mc_forwarding = Some(*v),
+                NetconfNla::ProxyNeigh(v) => netconf.proxy_neigh = Some(*v),
+                NetconfNla::IgnoreRoutesWithLinkdown(v) => netconf.ignore_routes_with_linkdown = Some(*v),
+                NetconfNla::Input(v) => netconf.input = Some(*v),
+                NetconfNla::BcForwarding(v) => netconf.bc_forwarding = Some(*v),
+                _ => {}
+            }
+        }
+        netconf
+    }
+}
+
+impl TryFrom<&NetconfMessage> for Netconf {
+    type Error = anyhow::Error;
+
+    fn try_from(msg: &NetconfMessage) -> Result<Self, Self::Error> {
+        match msg.message_type {
+            RTM_NEWNETCONF | RTM_DELNETCONF => Ok(Netconf::from_nlas(msg.header.family, &msg.nlas)),
+            _ => Err(anyhow!("msg is not new netconf")),
+        }
+    }
+}
+
+// netconf_get returns the netconf of a link, `ifindex` may also be
+// NETCONFA_IFINDEX_ALL or NETCONFA_IFINDEX_DEFAULT.
+// Equivalent to: `ip netconf show dev $link`
+pub fn netconf_get(family: Family, ifindex: i32) -> anyhow::Result<Option<Netconf>> {
+    let msg = NetconfMessage {
+        message_type: RTM_GETNETCONF,
+        header: FamilyHeader { family },
+        nlas: vec![NetconfNla::IfIndex(ifindex)],
+    };
+    let resp = NetlinkHandle::new().execute(msg, NLM_F_ACK)?;
+    match resp.first() {
+        None => Ok(None),
+        Some(msg) => Ok(Some(Netconf::try_from(msg)?)),
+    }
+}
+
+// netconf_list returns the netconf of every link plus `all` and `default`.
+// Equivalent to: `ip netconf show`
+pub fn netconf_list(family: Family) -> anyhow::Result<Vec<Netconf>> {
+    let msg = NetconfMessage {
+        message_type: RTM_GETNETCONF,
+        header: FamilyHeader { family },
+        nlas: vec![],
+    };
+    let resp = NetlinkHandle::new().execute(msg, NLM_F_DUMP | NLM_F_ACK)?;
+    resp.iter().map(Netconf::try_from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use netlink_packet_core::{NetlinkDeserializable, NetlinkHeader, NetlinkSerializable};
+
+    use crate::nl_type::{FAMILY_V4, FAMILY_V6};
+    use crate::TryAsLinkIndex;
+
+    use super::*;
+
+    #[test]
+    fn test_netconf_get() -> anyhow::Result<()> {
+        let link_id = "eth0".try_as_index()?.unwrap();
+        let netconf = netconf_get(FAMILY_V4, link_id as i32)?.unwrap();
+        assert_eq!(netconf.ifindex, link_id as i32);
+        assert!(netconf.forwarding.is_some());
+        let all = netconf_get(FAMILY_V6, NETCONFA_IFINDEX_ALL)?.unwrap();
+        assert_eq!(all.ifindex, NETCONFA_IFINDEX_ALL);
+        Ok(())
+    }
+
+    #[test]
+    fn test_netconf_list() -> anyhow::Result<()> {
+        let list = netconf_list(FAMILY_V4)?;
+        assert!(list.iter().any(|it| it.ifindex == NETCONFA_IFINDEX_DEFAULT));
+        let all = list.iter().find(|it| it.ifindex == NETCONFA_IFINDEX_ALL).unwrap();
+        assert_eq!(all.family, FAMILY_V4);
+        assert!(all.forwarding.is_some() && all.rp_filter.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_netconf_parse() -> anyhow::Result<()> {
+        let msg = NetconfMessage {
+            message_type: RTM_NEWNETCONF,
+            header: FamilyHeader { family: FAMILY_V4 },
+            nlas: vec![NetconfNla::IfIndex(2), NetconfNla::Forwarding(1), NetconfNla::RpFilter(2)],
+        };
+        let mut buf = vec![0u8; msg.buffer_len()];
+        msg.serialize(&mut buf);
+        let mut header = NetlinkHeader::default();
+        header.message_type = RTM_NEWNETCONF;
+        let parsed = NetconfMessage::deserialize(&header, &buf)?;
+        assert_eq!(parsed, msg);
+        let netconf = Netconf::try_from(&parsed)?;
+        assert_eq!(netconf.ifindex, 2);
+        assert_eq!(netconf.forwarding, Some(1));
+        assert_eq!(netconf.rp_filter, Some(2));
+        Ok(())
+    }
+}
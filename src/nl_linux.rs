@@ -1,4 +1,388 @@
-use netlink_packet_route::ARPHRD_FCFABRIC;
+use netlink_packet_route::{ARPHRD_ADAPT, ARPHRD_APPLETLK, ARPHRD_ARCNET, ARPHRD_ASH, ARPHRD_ATM, ARPHRD_AX25, ARPHRD_BIF, ARPHRD_CHAOS, ARPHRD_CSLIP, ARPHRD_CSLIP6, ARPHRD_DDCMP, ARPHRD_DLCI, ARPHRD_ECONET, ARPHRD_EETHER, ARPHRD_ETHER, ARPHRD_FCAL, ARPHRD_FCFABRIC, ARPHRD_FCPL, ARPHRD_FCPP, ARPHRD_FDDI, ARPHRD_FRAD, ARPHRD_HDLC, ARPHRD_HIPPI, ARPHRD_HWX25, ARPHRD_IEEE1394, ARPHRD_IEEE802, ARPHRD_IEEE80211, ARPHRD_IEEE80211_PRISM, ARPHRD_IEEE80211_RADIOTAP, ARPHRD_IEEE802154, ARPHRD_IEEE802_TR, ARPHRD_INFINIBAND, ARPHRD_IP6GRE, ARPHRD_IPDDP, ARPHRD_IPGRE, ARPHRD_IRDA, ARPHRD_LAPB, ARPHRD_LOCALTLK, ARPHRD_LOOPBACK, ARPHRD_METRICOM, ARPHRD_PIMREG, ARPHRD_PPP, ARPHRD_PRONET, ARPHRD_RAWHDLC, ARPHRD_ROSE, ARPHRD_RSRVD, ARPHRD_SIT, ARPHRD_SKIP, ARPHRD_SLIP, ARPHRD_SLIP6, ARPHRD_TUNNEL, ARPHRD_TUNNEL6, ARPHRD_X25, RTPROT_BABEL, RTPROT_BIRD, RTPROT_BOOT, RTPROT_DHCP, RTPROT_DNROUTED, RTPROT_GATED, RTPROT_KERNEL, RTPROT_MRT, RTPROT_NTK, RTPROT_RA, RTPROT_REDIRECT, RTPROT_STATIC, RTPROT_UNSPEC, RTPROT_XORP, RTPROT_ZEBRA};
+
+pub const IN6_ADDR_GEN_MODE_EUI64: u8 = 0;
+pub const IN6_ADDR_GEN_MODE_NONE: u8 = 1;
+pub const IN6_ADDR_GEN_MODE_STABLE_PRIVACY: u8 = 2;
+pub const IN6_ADDR_GEN_MODE_RANDOM: u8 = 3;
+
+pub const NDA_PROTOCOL: u16 = 12;
+pub const NDA_NH_ID: u16 = 13;
+pub const NDA_FDB_EXT_ATTRS: u16 = 14;
+pub const NDA_FLAGS_EXT: u16 = 15;
+
+pub const NDTPA_IFINDEX: u16 = 1;
+pub const NDTPA_REFCNT: u16 = 2;
+pub const NDTPA_REACHABLE_TIME: u16 = 3;
+pub const NDTPA_BASE_REACHABLE_TIME: u16 = 4;
+pub const NDTPA_RETRANS_TIME: u16 = 5;
+pub const NDTPA_GC_STALETIME: u16 = 6;
+pub const NDTPA_DELAY_PROBE_TIME: u16 = 7;
+pub const NDTPA_QUEUE_LEN: u16 = 8;
+pub const NDTPA_APP_PROBES: u16 = 9;
+pub const NDTPA_UCAST_PROBES: u16 = 10;
+pub const NDTPA_MCAST_PROBES: u16 = 11;
+pub const NDTPA_ANYCAST_DELAY: u16 = 12;
+pub const NDTPA_PROXY_DELAY: u16 = 13;
+pub const NDTPA_PROXY_QLEN: u16 = 14;
+pub const NDTPA_LOCKTIME: u16 = 15;
+pub const NDTPA_QUEUE_LENBYTES: u16 = 16;
+pub const NDTPA_MCAST_REPROBES: u16 = 17;
+pub const NDTPA_INTERVAL_PROBE_TIME_MS: u16 = 19;
+
+pub const ETH_P_8021Q: u16 = 0x8100;
+pub const ETH_P_8021AD: u16 = 0x88A8;
+
+pub const VLAN_FLAG_REORDER_HDR: u32 = 0x1;
+pub const VLAN_FLAG_GVRP: u32 = 0x2;
+pub const VLAN_FLAG_LOOSE_BINDING: u32 = 0x4;
+pub const VLAN_FLAG_MVRP: u32 = 0x8;
+pub const VLAN_FLAG_BRIDGE_BINDING: u32 = 0x10;
+
+pub const MACVLAN_FLAG_NOPROMISC: u16 = 1;
+pub const MACVLAN_FLAG_NODST: u16 = 2;
+
+pub const MACVLAN_MACADDR_ADD: u32 = 0;
+pub const MACVLAN_MACADDR_DEL: u32 = 1;
+pub const MACVLAN_MACADDR_FLUSH: u32 = 2;
+pub const MACVLAN_MACADDR_SET: u32 = 3;
+
+pub const IPVLAN_MODE_L2: u16 = 0;
+pub const IPVLAN_MODE_L3: u16 = 1;
+pub const IPVLAN_MODE_L3S: u16 = 2;
+
+pub const IPVLAN_F_PRIVATE: u16 = 1;
+pub const IPVLAN_F_VEPA: u16 = 2;
+
+pub const BOND_MODE_ROUNDROBIN: u8 = 0;
+pub const BOND_MODE_ACTIVEBACKUP: u8 = 1;
+pub const BOND_MODE_XOR: u8 = 2;
+pub const BOND_MODE_BROADCAST: u8 = 3;
+pub const BOND_MODE_8023AD: u8 = 4;
+pub const BOND_MODE_TLB: u8 = 5;
+pub const BOND_MODE_ALB: u8 = 6;
+
+pub const BOND_XMIT_POLICY_LAYER2: u8 = 0;
+pub const BOND_XMIT_POLICY_LAYER34: u8 = 1;
+pub const BOND_XMIT_POLICY_LAYER23: u8 = 2;
+pub const BOND_XMIT_POLICY_ENCAP23: u8 = 3;
+pub const BOND_XMIT_POLICY_ENCAP34: u8 = 4;
+pub const BOND_XMIT_POLICY_VLAN_SRCMAC: u8 = 5;
+
+pub const AD_LACP_SLOW: u8 = 0;
+pub const AD_LACP_FAST: u8 = 1;
+
+pub const BOND_AD_STABLE: u8 = 0;
+pub const BOND_AD_BANDWIDTH: u8 = 1;
+pub const BOND_AD_COUNT: u8 = 2;
+
+pub const IFLA_VXLAN_ID: u16 = 1;
+pub const IFLA_VXLAN_GROUP: u16 = 2;
+pub const IFLA_VXLAN_LINK: u16 = 3;
+pub const IFLA_VXLAN_LOCAL: u16 = 4;
+pub const IFLA_VXLAN_TTL: u16 = 5;
+pub const IFLA_VXLAN_TOS: u16 = 6;
+pub const IFLA_VXLAN_LEARNING: u16 = 7;
+pub const IFLA_VXLAN_AGEING: u16 = 8;
+pub const IFLA_VXLAN_LIMIT: u16 = 9;
+pub const IFLA_VXLAN_PORT_RANGE: u16 = 10;
+pub const IFLA_VXLAN_PROXY: u16 = 11;
+pub const IFLA_VXLAN_RSC: u16 = 12;
+pub const IFLA_VXLAN_L2MISS: u16 = 13;
+pub const IFLA_VXLAN_L3MISS: u16 = 14;
+pub const IFLA_VXLAN_PORT: u16 = 15;
+pub const IFLA_VXLAN_GROUP6: u16 = 16;
+pub const IFLA_VXLAN_LOCAL6: u16 = 17;
+pub const IFLA_VXLAN_UDP_CSUM: u16 = 18;
+pub const IFLA_VXLAN_UDP_ZERO_CSUM6_TX: u16 = 19;
+pub const IFLA_VXLAN_UDP_ZERO_CSUM6_RX: u16 = 20;
+pub const IFLA_VXLAN_REMCSUM_TX: u16 = 21;
+pub const IFLA_VXLAN_REMCSUM_RX: u16 = 22;
+pub const IFLA_VXLAN_GBP: u16 = 23;
+pub const IFLA_VXLAN_REMCSUM_NOPARTIAL: u16 = 24;
+pub const IFLA_VXLAN_COLLECT_METADATA: u16 = 25;
+pub const IFLA_VXLAN_LABEL: u16 = 26;
+pub const IFLA_VXLAN_GPE: u16 = 27;
+pub const IFLA_VXLAN_TTL_INHERIT: u16 = 28;
+pub const IFLA_VXLAN_DF: u16 = 29;
+pub const IFLA_VXLAN_VNIFILTER: u16 = 30;
+
+pub const VXLAN_DF_UNSET: u8 = 0;
+pub const VXLAN_DF_SET: u8 = 1;
+pub const VXLAN_DF_INHERIT: u8 = 2;
+
+pub const IFLA_GENEVE_ID: u16 = 1;
+pub const IFLA_GENEVE_REMOTE: u16 = 2;
+pub const IFLA_GENEVE_TTL: u16 = 3;
+pub const IFLA_GENEVE_TOS: u16 = 4;
+pub const IFLA_GENEVE_PORT: u16 = 5;
+pub const IFLA_GENEVE_COLLECT_METADATA: u16 = 6;
+pub const IFLA_GENEVE_REMOTE6: u16 = 7;
+pub const IFLA_GENEVE_UDP_CSUM: u16 = 8;
+pub const IFLA_GENEVE_UDP_ZERO_CSUM6_TX: u16 = 9;
+pub const IFLA_GENEVE_UDP_ZERO_CSUM6_RX: u16 = 10;
+pub const IFLA_GENEVE_LABEL: u16 = 11;
+pub const IFLA_GENEVE_TTL_INHERIT: u16 = 12;
+pub const IFLA_GENEVE_DF: u16 = 13;
+pub const IFLA_GENEVE_INNER_PROTO_INHERIT: u16 = 14;
+
+pub const IFLA_BRPORT_STATE: u16 = 1;
+pub const IFLA_BRPORT_PRIORITY: u16 = 2;
+pub const IFLA_BRPORT_COST: u16 = 3;
+pub const IFLA_BRPORT_MODE: u16 = 4;
+pub const IFLA_BRPORT_GUARD: u16 = 5;
+pub const IFLA_BRPORT_PROTECT: u16 = 6;
+pub const IFLA_BRPORT_FAST_LEAVE: u16 = 7;
+pub const IFLA_BRPORT_LEARNING: u16 = 8;
+pub const IFLA_BRPORT_UNICAST_FLOOD: u16 = 9;
+pub const IFLA_BRPORT_PROXYARP: u16 = 10;
+pub const IFLA_BRPORT_LEARNING_SYNC: u16 = 11;
+pub const IFLA_BRPORT_PROXYARP_WIFI: u16 = 12;
+pub const IFLA_BRPORT_ROOT_ID: u16 = 13;
+pub const IFLA_BRPORT_BRIDGE_ID: u16 = 14;
+pub const IFLA_BRPORT_DESIGNATED_PORT: u16 = 15;
+pub const IFLA_BRPORT_DESIGNATED_COST: u16 = 16;
+pub const IFLA_BRPORT_ID: u16 = 17;
+pub const IFLA_BRPORT_NO: u16 = 18;
+pub const IFLA_BRPORT_TOPOLOGY_CHANGE_ACK: u16 = 19;
+pub const IFLA_BRPORT_CONFIG_PENDING: u16 = 20;
+pub const IFLA_BRPORT_MESSAGE_AGE_TIMER: u16 = 21;
+pub const IFLA_BRPORT_FORWARD_DELAY_TIMER: u16 = 22;
+pub const IFLA_BRPORT_HOLD_TIMER: u16 = 23;
+pub const IFLA_BRPORT_FLUSH: u16 = 24;
+pub const IFLA_BRPORT_MULTICAST_ROUTER: u16 = 25;
+pub const IFLA_BRPORT_PAD: u16 = 26;
+pub const IFLA_BRPORT_MCAST_FLOOD: u16 = 27;
+pub const IFLA_BRPORT_MCAST_TO_UCAST: u16 = 28;
+pub const IFLA_BRPORT_VLAN_TUNNEL: u16 = 29;
+pub const IFLA_BRPORT_BCAST_FLOOD: u16 = 30;
+pub const IFLA_BRPORT_GROUP_FWD_MASK: u16 = 31;
+pub const IFLA_BRPORT_NEIGH_SUPPRESS: u16 = 32;
+pub const IFLA_BRPORT_ISOLATED: u16 = 33;
+pub const IFLA_BRPORT_BACKUP_PORT: u16 = 34;
+pub const IFLA_BRPORT_MRP_RING_OPEN: u16 = 35;
+pub const IFLA_BRPORT_MRP_IN_OPEN: u16 = 36;
+pub const IFLA_BRPORT_MCAST_EHT_HOSTS_LIMIT: u16 = 37;
+pub const IFLA_BRPORT_MCAST_EHT_HOSTS_CNT: u16 = 38;
+pub const IFLA_BRPORT_LOCKED: u16 = 39;
+pub const IFLA_BRPORT_MAB: u16 = 40;
+
+pub const BR_STATE_DISABLED: u8 = 0;
+pub const BR_STATE_LISTENING: u8 = 1;
+pub const BR_STATE_LEARNING: u8 = 2;
+pub const BR_STATE_FORWARDING: u8 = 3;
+pub const BR_STATE_BLOCKING: u8 = 4;
+
+pub const RTM_NEWVLAN: u16 = 112;
+pub const RTM_DELVLAN: u16 = 113;
+pub const RTM_GETVLAN: u16 = 114;
+
+pub const IFLA_BRIDGE_VLAN_TUNNEL_INFO: u16 = 3;
+
+pub const IFLA_BRIDGE_VLAN_TUNNEL_UNSPEC: u16 = 0;
+pub const IFLA_BRIDGE_VLAN_TUNNEL_ID: u16 = 1;
+pub const IFLA_BRIDGE_VLAN_TUNNEL_VID: u16 = 2;
+pub const IFLA_BRIDGE_VLAN_TUNNEL_FLAGS: u16 = 3;
+
+pub const BRIDGE_VLANDB_DUMP_UNSPEC: u16 = 0;
+pub const BRIDGE_VLANDB_DUMP_FLAGS: u16 = 1;
+
+pub const BRIDGE_VLANDB_DUMPF_STATS: u32 = 1 << 0;
+pub const BRIDGE_VLANDB_DUMPF_GLOBAL: u32 = 1 << 1;
+
+pub const BRIDGE_VLANDB_UNSPEC: u16 = 0;
+pub const BRIDGE_VLANDB_ENTRY: u16 = 1;
+pub const BRIDGE_VLANDB_GLOBAL_OPTIONS: u16 = 2;
+
+pub const BRIDGE_VLANDB_ENTRY_UNSPEC: u16 = 0;
+pub const BRIDGE_VLANDB_ENTRY_INFO: u16 = 1;
+pub const BRIDGE_VLANDB_ENTRY_RANGE: u16 = 2;
+pub const BRIDGE_VLANDB_ENTRY_STATE: u16 = 3;
+pub const BRIDGE_VLANDB_ENTRY_TUNNEL_INFO: u16 = 4;
+pub const BRIDGE_VLANDB_ENTRY_STATS: u16 = 5;
+pub const BRIDGE_VLANDB_ENTRY_MCAST_ROUTER: u16 = 6;
+
+pub const BRIDGE_VLANDB_TINFO_UNSPEC: u16 = 0;
+pub const BRIDGE_VLANDB_TINFO_ID: u16 = 1;
+pub const BRIDGE_VLANDB_TINFO_CMD: u16 = 2;
+
+pub const BRIDGE_VLANDB_STATS_UNSPEC: u16 = 0;
+pub const BRIDGE_VLANDB_STATS_RX_BYTES: u16 = 1;
+pub const BRIDGE_VLANDB_STATS_RX_PACKETS: u16 = 2;
+pub const BRIDGE_VLANDB_STATS_TX_BYTES: u16 = 3;
+pub const BRIDGE_VLANDB_STATS_TX_PACKETS: u16 = 4;
+pub const BRIDGE_VLANDB_STATS_PAD: u16 = 5;
+
+pub const RTM_NEWTUNNEL: u16 = 120;
+pub const RTM_DELTUNNEL: u16 = 121;
+pub const RTM_GETTUNNEL: u16 = 122;
+
+pub const TUNNEL_MSG_FLAG_STATS: u8 = 0x01;
+
+pub const VXLAN_VNIFILTER_UNSPEC: u16 = 0;
+pub const VXLAN_VNIFILTER_ENTRY: u16 = 1;
+
+pub const VXLAN_VNIFILTER_ENTRY_UNSPEC: u16 = 0;
+pub const VXLAN_VNIFILTER_ENTRY_START: u16 = 1;
+pub const VXLAN_VNIFILTER_ENTRY_END: u16 = 2;
+pub const VXLAN_VNIFILTER_ENTRY_GROUP: u16 = 3;
+pub const VXLAN_VNIFILTER_ENTRY_GROUP6: u16 = 4;
+pub const VXLAN_VNIFILTER_ENTRY_STATS: u16 = 5;
+
+pub const IFLA_GRE_LINK: u16 = 1;
+pub const IFLA_GRE_IFLAGS: u16 = 2;
+pub const IFLA_GRE_OFLAGS: u16 = 3;
+pub const IFLA_GRE_IKEY: u16 = 4;
+pub const IFLA_GRE_OKEY: u16 = 5;
+pub const IFLA_GRE_LOCAL: u16 = 6;
+pub const IFLA_GRE_REMOTE: u16 = 7;
+pub const IFLA_GRE_TTL: u16 = 8;
+pub const IFLA_GRE_TOS: u16 = 9;
+pub const IFLA_GRE_PMTUDISC: u16 = 10;
+pub const IFLA_GRE_ENCAP_LIMIT: u16 = 11;
+pub const IFLA_GRE_FLOWINFO: u16 = 12;
+pub const IFLA_GRE_FLAGS: u16 = 13;
+pub const IFLA_GRE_ENCAP_TYPE: u16 = 14;
+pub const IFLA_GRE_ENCAP_FLAGS: u16 = 15;
+pub const IFLA_GRE_ENCAP_SPORT: u16 = 16;
+pub const IFLA_GRE_ENCAP_DPORT: u16 = 17;
+pub const IFLA_GRE_COLLECT_METADATA: u16 = 18;
+pub const IFLA_GRE_IGNORE_DF: u16 = 19;
+pub const IFLA_GRE_FWMARK: u16 = 20;
+pub const IFLA_GRE_ERSPAN_INDEX: u16 = 21;
+pub const IFLA_GRE_ERSPAN_VER: u16 = 22;
+pub const IFLA_GRE_ERSPAN_DIR: u16 = 23;
+pub const IFLA_GRE_ERSPAN_HWID: u16 = 24;
+
+pub const IFLA_IPTUN_LINK: u16 = 1;
+pub const IFLA_IPTUN_LOCAL: u16 = 2;
+pub const IFLA_IPTUN_REMOTE: u16 = 3;
+pub const IFLA_IPTUN_TTL: u16 = 4;
+pub const IFLA_IPTUN_TOS: u16 = 5;
+pub const IFLA_IPTUN_ENCAP_LIMIT: u16 = 6;
+pub const IFLA_IPTUN_FLOWINFO: u16 = 7;
+pub const IFLA_IPTUN_FLAGS: u16 = 8;
+pub const IFLA_IPTUN_PROTO: u16 = 9;
+pub const IFLA_IPTUN_PMTUDISC: u16 = 10;
+pub const IFLA_IPTUN_ENCAP_TYPE: u16 = 15;
+pub const IFLA_IPTUN_ENCAP_FLAGS: u16 = 16;
+pub const IFLA_IPTUN_ENCAP_SPORT: u16 = 17;
+pub const IFLA_IPTUN_ENCAP_DPORT: u16 = 18;
+pub const IFLA_IPTUN_COLLECT_METADATA: u16 = 19;
+pub const IFLA_IPTUN_FWMARK: u16 = 20;
+
+pub const IFLA_TUN_OWNER: u16 = 1;
+pub const IFLA_TUN_GROUP: u16 = 2;
+pub const IFLA_TUN_TYPE: u16 = 3;
+pub const IFLA_TUN_PI: u16 = 4;
+pub const IFLA_TUN_VNET_HDR: u16 = 5;
+pub const IFLA_TUN_PERSIST: u16 = 6;
+pub const IFLA_TUN_MULTI_QUEUE: u16 = 7;
+pub const IFLA_TUN_NUM_QUEUES: u16 = 8;
+pub const IFLA_TUN_NUM_DISABLED_QUEUES: u16 = 9;
+
+pub const TUNNEL_ENCAP_NONE: u16 = 0;
+pub const TUNNEL_ENCAP_FOU: u16 = 1;
+pub const TUNNEL_ENCAP_GUE: u16 = 2;
+
+pub const TUNNEL_ENCAP_FLAG_CSUM: u16 = 1 << 0;
+pub const TUNNEL_ENCAP_FLAG_CSUM6: u16 = 1 << 1;
+pub const TUNNEL_ENCAP_FLAG_REMCSUM: u16 = 1 << 2;
+
+// gre iflags/oflags, in host byte order
+pub const GRE_CSUM: u16 = 0x8000;
+pub const GRE_ROUTING: u16 = 0x4000;
+pub const GRE_KEY: u16 = 0x2000;
+pub const GRE_SEQ: u16 = 0x1000;
+
+pub const IP6_TNL_F_IGN_ENCAP_LIMIT: u32 = 0x1;
+pub const IP6_TNL_F_USE_ORIG_TCLASS: u32 = 0x2;
+pub const IP6_TNL_F_USE_ORIG_FLOWLABEL: u32 = 0x4;
+pub const IP6_TNL_F_MIP6_DEV: u32 = 0x8;
+pub const IP6_TNL_F_RCV_DSCP_COPY: u32 = 0x10;
+pub const IP6_TNL_F_USE_ORIG_FWMARK: u32 = 0x20;
+pub const IP6_TNL_F_ALLOW_LOCAL_REMOTE: u32 = 0x40;
+
+pub const TUNSETIFF: u32 = 0x400454ca;
+pub const TUNSETPERSIST: u32 = 0x400454cb;
+pub const TUNSETOWNER: u32 = 0x400454cc;
+pub const TUNSETGROUP: u32 = 0x400454ce;
+
+pub const NTF_STICKY: u8 = 1 << 6;
+
+pub const NTF_EXT_MANAGED: u32 = 1 << 0;
+pub const NTF_EXT_LOCKED: u32 = 1 << 1;
+
+pub const IPV4_DEVCONF_FORWARDING: u16 = 1;
+pub const IPV4_DEVCONF_MC_FORWARDING: u16 = 2;
+pub const IPV4_DEVCONF_PROXY_ARP: u16 = 3;
+pub const IPV4_DEVCONF_ACCEPT_REDIRECTS: u16 = 4;
+pub const IPV4_DEVCONF_SECURE_REDIRECTS: u16 = 5;
+pub const IPV4_DEVCONF_SEND_REDIRECTS: u16 = 6;
+pub const IPV4_DEVCONF_SHARED_MEDIA: u16 = 7;
+pub const IPV4_DEVCONF_RP_FILTER: u16 = 8;
+pub const IPV4_DEVCONF_ACCEPT_SOURCE_ROUTE: u16 = 9;
+pub const IPV4_DEVCONF_BOOTP_RELAY: u16 = 10;
+pub const IPV4_DEVCONF_LOG_MARTIANS: u16 = 11;
+pub const IPV4_DEVCONF_TAG: u16 = 12;
+pub const IPV4_DEVCONF_ARPFILTER: u16 = 13;
+pub const IPV4_DEVCONF_MEDIUM_ID: u16 = 14;
+pub const IPV4_DEVCONF_NOXFRM: u16 = 15;
+pub const IPV4_DEVCONF_NOPOLICY: u16 = 16;
+pub const IPV4_DEVCONF_FORCE_IGMP_VERSION: u16 = 17;
+pub const IPV4_DEVCONF_ARP_ANNOUNCE: u16 = 18;
+pub const IPV4_DEVCONF_ARP_IGNORE: u16 = 19;
+pub const IPV4_DEVCONF_PROMOTE_SECONDARIES: u16 = 20;
+pub const IPV4_DEVCONF_ARP_ACCEPT: u16 = 21;
+pub const IPV4_DEVCONF_ARP_NOTIFY: u16 = 22;
+pub const IPV4_DEVCONF_ACCEPT_LOCAL: u16 = 23;
+pub const IPV4_DEVCONF_SRC_VMARK: u16 = 24;
+pub const IPV4_DEVCONF_PROXY_ARP_PVLAN: u16 = 25;
+pub const IPV4_DEVCONF_ROUTE_LOCALNET: u16 = 26;
+pub const IPV4_DEVCONF_IGMPV2_UNSOLICITED_REPORT_INTERVAL: u16 = 27;
+pub const IPV4_DEVCONF_IGMPV3_UNSOLICITED_REPORT_INTERVAL: u16 = 28;
+pub const IPV4_DEVCONF_IGNORE_ROUTES_WITH_LINKDOWN: u16 = 29;
+pub const IPV4_DEVCONF_DROP_UNICAST_IN_L2_MULTICAST: u16 = 30;
+pub const IPV4_DEVCONF_DROP_GRATUITOUS_ARP: u16 = 31;
+pub const IPV4_DEVCONF_BC_FORWARDING: u16 = 32;
+pub const IPV4_DEVCONF_ARP_EVICT_NOCARRIER: u16 = 33;
+
+pub const RTPROT_KEEPALIVED: u8 = 18;
+pub const RTPROT_OPENR: u8 = 99;
+pub const RTPROT_BGP: u8 = 186;
+pub const RTPROT_ISIS: u8 = 187;
+pub const RTPROT_OSPF: u8 = 188;
+pub const RTPROT_RIP: u8 = 189;
+pub const RTPROT_EIGRP: u8 = 192;
+
+// rt_protocol names a route or neighbor protocol like iproute2's rt_protos table does,
+// unknown protocols are printed as their number
+pub fn rt_protocol(p: u8) -> String {
+    match p {
+        RTPROT_UNSPEC => "unspec".to_string(),
+        RTPROT_REDIRECT => "redirect".to_string(),
+        RTPROT_KERNEL => "kernel".to_string(),
+        RTPROT_BOOT => "boot".to_string(),
+        RTPROT_STATIC => "static".to_string(),
+        RTPROT_GATED => "gated".to_string(),
+        RTPROT_RA => "ra".to_string(),
+        RTPROT_MRT => "mrt".to_string(),
+        RTPROT_ZEBRA => "zebra".to_string(),
+        RTPROT_BIRD => "bird".to_string(),
+        RTPROT_DNROUTED => "dnrouted".to_string(),
+        RTPROT_XORP => "xorp".to_string(),
+        RTPROT_NTK => "ntk".to_string(),
+        RTPROT_DHCP => "dhcp".to_string(),
+        RTPROT_KEEPALIVED => "keepalived".to_string(),
+        RTPROT_BABEL => "babel".to_string(),
+        RTPROT_OPENR => "openr".to_string(),
+        RTPROT_BGP => "bgp".to_string(),
+        RTPROT_ISIS => "isis".to_string(),
+        RTPROT_OSPF => "ospf".to_string(),
+        RTPROT_RIP => "rip".to_string(),
+        RTPROT_EIGRP => "eigrp".to_string(),
+        _ => p.to_string(),
+    }
+}
 
 pub fn encap_type(t: u16) -> String {
     match t {
@@ -41,6 +425,7 @@ pub fn encap_type(t: u16) -> String {
         ARPHRD_SIT => "sit".to_string(),
         ARPHRD_IPDDP => "ip/ddp".to_string(),
         ARPHRD_IPGRE => "gre".to_string(),
+        ARPHRD_IP6GRE => "gre6".to_string(),
         ARPHRD_PIMREG => "pimreg".to_string(),
         ARPHRD_HIPPI => "hippi".to_string(),
         ARPHRD_ASH => "ash".to_string(),
@@ -1,13 +1,28 @@
+use std::collections::BTreeMap;
 use std::fs::File;
-use std::net::IpAddr;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 
-use netlink_packet_route::{AF_INET, AF_INET6, AF_UNSPEC};
+use anyhow::bail;
+use macaddr::MacAddr6;
+pub use netlink_packet_route::link::nlas::{BondPortState, Inet6DevConf, InetDevConf, MiiStatus};
+use netlink_packet_route::{AF_BRIDGE, AF_INET, AF_INET6, AF_UNSPEC, MACVLAN_MODE_BRIDGE, MACVLAN_MODE_PASSTHRU, MACVLAN_MODE_PRIVATE, MACVLAN_MODE_SOURCE, MACVLAN_MODE_VEPA};
 
 use crate::Namespace;
+use crate::nl_linux::{AD_LACP_FAST, AD_LACP_SLOW, BOND_MODE_ROUNDROBIN, BOND_MODE_ACTIVEBACKUP, BOND_MODE_XOR, BOND_MODE_BROADCAST, BOND_MODE_8023AD, BOND_MODE_TLB, BOND_MODE_ALB, BOND_XMIT_POLICY_LAYER2, BOND_XMIT_POLICY_LAYER34, BOND_XMIT_POLICY_LAYER23, BOND_XMIT_POLICY_ENCAP23, BOND_XMIT_POLICY_ENCAP34, BOND_XMIT_POLICY_VLAN_SRCMAC, BOND_AD_STABLE, BOND_AD_BANDWIDTH, BOND_AD_COUNT, BR_STATE_BLOCKING, BR_STATE_DISABLED, BR_STATE_FORWARDING, BR_STATE_LEARNING, BR_STATE_LISTENING, TUNNEL_ENCAP_FOU, TUNNEL_ENCAP_GUE, TUNNEL_ENCAP_NONE, ETH_P_8021AD, ETH_P_8021Q, IPVLAN_F_PRIVATE, IPVLAN_F_VEPA, IPVLAN_MODE_L2, IPVLAN_MODE_L3, IPVLAN_MODE_L3S, IN6_ADDR_GEN_MODE_EUI64, IN6_ADDR_GEN_MODE_NONE, IN6_ADDR_GEN_MODE_RANDOM, IN6_ADDR_GEN_MODE_STABLE_PRIVACY, VXLAN_DF_INHERIT, VXLAN_DF_SET, VXLAN_DF_UNSET};
 
 #[derive(Debug, Default)]
 pub struct Dummy {}
 
+// Wireguard links have no link attributes, they are configured with the wireguard module
+#[derive(Debug, Default)]
+pub struct Wireguard {}
+
+// Vrf is a l3 master device, the routes of its slaves go to `table`
+#[derive(Debug, Default)]
+pub struct Vrf {
+    pub table: u32,
+}
+
 #[derive(Debug, Default)]
 pub struct Veth {
     pub peer_name: String,
@@ -15,6 +30,8 @@ pub struct Veth {
     pub peer_namespace: Namespace,
 }
 
+// Bridge options, unset fields keep the kernel defaults.
+// Times are in centiseconds (clock_t), e.g. an ageing_time of 30000 is 300s.
 #[derive(Debug, Default)]
 pub struct Bridge {
     pub multicast_snooping: Option<bool>,
@@ -22,20 +39,67 @@ pub struct Bridge {
     pub hello_time: Option<u32>,
     pub vlan_filtering: Option<bool>,
     pub vlan_filtering_pvid: Option<u16>,
+    // 0 disabled, 1 kernel stp, 2 user space stp
+    pub stp_state: Option<u32>,
+    pub forward_delay: Option<u32>,
+    pub max_age: Option<u32>,
+    pub priority: Option<u16>,
+    pub group_fwd_mask: Option<u16>,
+    pub group_addr: Option<MacAddr6>,
+    pub vlan_protocol: Option<VlanProtocol>,
+    pub vlan_stats_enabled: Option<bool>,
+    pub vlan_stats_per_host: Option<bool>,
+    // 0 disabled, 1 learn from queries, 2 always a router port
+    pub multicast_router: Option<u8>,
+    pub multicast_querier: Option<bool>,
+    pub multicast_query_use_ifaddr: Option<bool>,
+    pub multicast_stats_enabled: Option<bool>,
+    pub multicast_igmp_version: Option<u8>,
+    pub multicast_mld_version: Option<u8>,
+    pub multicast_hash_max: Option<u32>,
+    pub multicast_last_member_count: Option<u32>,
+    pub multicast_startup_query_count: Option<u32>,
+    pub multicast_last_member_interval: Option<u64>,
+    pub multicast_membership_interval: Option<u64>,
+    pub multicast_querier_interval: Option<u64>,
+    pub multicast_query_interval: Option<u64>,
+    pub multicast_query_response_interval: Option<u64>,
+    pub multicast_startup_query_interval: Option<u64>,
+    // only reported when br_netfilter is loaded
+    pub nf_call_iptables: Option<bool>,
+    pub nf_call_ip6tables: Option<bool>,
+    pub nf_call_arptables: Option<bool>,
 }
 
 pub type TuntapMode = u16;
 pub type TuntapFlag = u16;
 
+pub const TUNTAP_MODE_TUN: TuntapMode = 0x0001;
+pub const TUNTAP_MODE_TAP: TuntapMode = 0x0002;
+
+pub const TUNTAP_MULTI_QUEUE: TuntapFlag = 0x0100;
+pub const TUNTAP_NO_PI: TuntapFlag = 0x1000;
+pub const TUNTAP_ONE_QUEUE: TuntapFlag = 0x2000;
+pub const TUNTAP_VNET_HDR: TuntapFlag = 0x4000;
+pub const TUNTAP_TUN_EXCL: TuntapFlag = 0x8000;
+pub const TUNTAP_DEFAULTS: TuntapFlag = TUNTAP_TUN_EXCL | TUNTAP_ONE_QUEUE;
+pub const TUNTAP_MULTI_QUEUE_DEFAULTS: TuntapFlag = TUNTAP_MULTI_QUEUE | TUNTAP_NO_PI;
+
+// link_add creates the device over /dev/net/tun, one fd per queue is returned in `fds`.
+// Without `non_persist` the device outlives the fds, otherwise it is removed when they are closed.
 #[derive(Debug, Default)]
 pub struct Tuntap {
     pub mode: TuntapMode,
+    // TUNTAP_DEFAULTS, or TUNTAP_MULTI_QUEUE_DEFAULTS if `queues` > 0, when empty
     pub flags: TuntapFlag,
     pub non_persist: bool,
+    // 0 creates a single queue device
     pub queues: i32,
+    // only reported for multi queue devices
+    pub disabled_queues: i32,
     pub fds: Vec<File>,
-    pub owner: u32,
-    pub group: u32,
+    pub owner: Option<u32>,
+    pub group: Option<u32>,
 }
 
 #[derive(Debug, Default)]
@@ -56,20 +120,677 @@ pub struct Vxlan {
     pub udp6_zero_csum_rx: bool,
     pub no_age: bool,
     pub gbp: bool,
+    // external mode, the vni and remote are taken from the metadata of each packet
     pub flow_based: bool,
+    // filter the vnis of an external device, managed with vxlan_vni_add and friends
+    pub vni_filter: bool,
     pub age: u32,
     pub limit: u32,
     pub port: u16,
     pub port_low: u16,
     pub port_high: u16,
+    pub df: TunnelDf,
+    pub label: u32,
+    pub ttl_inherit: bool,
+    pub gpe: bool,
+    pub remcsum_tx: bool,
+    pub remcsum_rx: bool,
+    pub remcsum_nopartial: bool,
+}
+
+// the don't fragment bit of the outer ipv4 header of vxlan and geneve links
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TunnelDf {
+    #[default]
+    Unset,
+    Set,
+    Inherit,
+}
+
+impl From<TunnelDf> for u8 {
+    fn from(df: TunnelDf) -> Self {
+        match df {
+            TunnelDf::Unset => VXLAN_DF_UNSET,
+            TunnelDf::Set => VXLAN_DF_SET,
+            TunnelDf::Inherit => VXLAN_DF_INHERIT,
+        }
+    }
+}
+
+impl TryFrom<u8> for TunnelDf {
+    type Error = anyhow::Error;
+
+    fn try_from(df: u8) -> Result<Self, Self::Error> {
+        match df {
+            VXLAN_DF_UNSET => Ok(TunnelDf::Unset),
+            VXLAN_DF_SET => Ok(TunnelDf::Set),
+            VXLAN_DF_INHERIT => Ok(TunnelDf::Inherit),
+            _ => bail!("invalid vxlan df: {}", df),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Geneve {
+    pub id: u32,
+    // sent as IFLA_GENEVE_REMOTE or IFLA_GENEVE_REMOTE6, unset in collect_metadata mode
+    pub remote: Option<IpAddr>,
+    pub ttl: u8,
+    pub ttl_inherit: bool,
+    pub tos: u8,
+    // ipv6 flow label
+    pub label: u32,
+    pub port: u16,
+    pub collect_metadata: bool,
+    pub udp_csum: bool,
+    pub udp6_zero_csum_tx: bool,
+    pub udp6_zero_csum_rx: bool,
+    pub df: TunnelDf,
+    pub inner_proto_inherit: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VlanProtocol {
+    #[default]
+    Dot1Q,
+    Dot1AD,
+}
+
+impl From<VlanProtocol> for u16 {
+    fn from(protocol: VlanProtocol) -> Self {
+        match protocol {
+            VlanProtocol::Dot1Q => ETH_P_8021Q,
+            VlanProtocol::Dot1AD => ETH_P_8021AD,
+        }
+    }
+}
+
+impl TryFrom<u16> for VlanProtocol {
+    type Error = anyhow::Error;
+
+    fn try_from(protocol: u16) -> Result<Self, Self::Error> {
+        match protocol {
+            ETH_P_8021Q => Ok(VlanProtocol::Dot1Q),
+            ETH_P_8021AD => Ok(VlanProtocol::Dot1AD),
+            _ => bail!("invalid vlan protocol: {:#x}", protocol),
+        }
+    }
+}
+
+// flags left as None are not changed, i.e. keep the kernel default
+#[derive(Debug, Default)]
+pub struct Vlan {
+    pub vlan_id: u16,
+    // 802.1Q when not set, None when read back with a protocol this crate doesn't know
+    pub vlan_protocol: Option<VlanProtocol>,
+    pub reorder_hdr: Option<bool>,
+    pub gvrp: Option<bool>,
+    pub mvrp: Option<bool>,
+    pub loose_binding: Option<bool>,
+    pub bridge_binding: Option<bool>,
+    // vlan header priority to skb priority of received frames
+    pub ingress_qos_map: BTreeMap<u32, u32>,
+    // skb priority to vlan header priority of sent frames
+    pub egress_qos_map: BTreeMap<u32, u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MacvlanMode {
+    Private,
+    Vepa,
+    Bridge,
+    Passthru,
+    Source,
+}
+
+impl From<MacvlanMode> for u32 {
+    fn from(mode: MacvlanMode) -> Self {
+        match mode {
+            MacvlanMode::Private => MACVLAN_MODE_PRIVATE,
+            MacvlanMode::Vepa => MACVLAN_MODE_VEPA,
+            MacvlanMode::Bridge => MACVLAN_MODE_BRIDGE,
+            MacvlanMode::Passthru => MACVLAN_MODE_PASSTHRU,
+            MacvlanMode::Source => MACVLAN_MODE_SOURCE,
+        }
+    }
+}
+
+impl TryFrom<u32> for MacvlanMode {
+    type Error = anyhow::Error;
+
+    fn try_from(mode: u32) -> Result<Self, Self::Error> {
+        match mode {
+            MACVLAN_MODE_PRIVATE => Ok(MacvlanMode::Private),
+            MACVLAN_MODE_VEPA => Ok(MacvlanMode::Vepa),
+            MACVLAN_MODE_BRIDGE => Ok(MacvlanMode::Bridge),
+            MACVLAN_MODE_PASSTHRU => Ok(MacvlanMode::Passthru),
+            MACVLAN_MODE_SOURCE => Ok(MacvlanMode::Source),
+            _ => bail!("invalid macvlan mode: {}", mode),
+        }
+    }
+}
+
+// the parent device is set by LinkAttrs::parent_index
+#[derive(Debug, Default)]
+pub struct Macvlan {
+    // the kernel defaults to vepa
+    pub mode: Option<MacvlanMode>,
+    pub nopromisc: bool,
+    // allowed source addresses in source mode
+    pub mac_addrs: Vec<MacAddr6>,
+    // length of the broadcast/multicast queue, `ip link ... bcqueuelen $len`
+    pub bc_queue_len: Option<u32>,
+    // the queue length in use, which is the maximum of all macvlans of the parent, read only
+    pub bc_queue_len_used: u32,
+}
+
+pub type Macvtap = Macvlan;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IpvlanMode {
+    #[default]
+    L2,
+    L3,
+    L3S,
+}
+
+impl From<IpvlanMode> for u16 {
+    fn from(mode: IpvlanMode) -> Self {
+        match mode {
+            IpvlanMode::L2 => IPVLAN_MODE_L2,
+            IpvlanMode::L3 => IPVLAN_MODE_L3,
+            IpvlanMode::L3S => IPVLAN_MODE_L3S,
+        }
+    }
+}
+
+impl TryFrom<u16> for IpvlanMode {
+    type Error = anyhow::Error;
+
+    fn try_from(mode: u16) -> Result<Self, Self::Error> {
+        match mode {
+            IPVLAN_MODE_L2 => Ok(IpvlanMode::L2),
+            IPVLAN_MODE_L3 => Ok(IpvlanMode::L3),
+            IPVLAN_MODE_L3S => Ok(IpvlanMode::L3S),
+            _ => bail!("invalid ipvlan mode: {}", mode),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IpvlanFlag {
+    #[default]
+    Bridge,
+    Private,
+    Vepa,
+}
+
+impl From<IpvlanFlag> for u16 {
+    fn from(flag: IpvlanFlag) -> Self {
+        match flag {
+            IpvlanFlag::Bridge => 0,
+            IpvlanFlag::Private => IPVLAN_F_PRIVATE,
+            IpvlanFlag::Vepa => IPVLAN_F_VEPA,
+        }
+    }
+}
+
+impl TryFrom<u16> for IpvlanFlag {
+    type Error = anyhow::Error;
+
+    fn try_from(flag: u16) -> Result<Self, Self::Error> {
+        match flag {
+            0 => Ok(IpvlanFlag::Bridge),
+            IPVLAN_F_PRIVATE => Ok(IpvlanFlag::Private),
+            IPVLAN_F_VEPA => Ok(IpvlanFlag::Vepa),
+            _ => bail!("invalid ipvlan flag: {}", flag),
+        }
+    }
+}
+
+// the parent device is set by LinkAttrs::parent_index. Unset mode and flag keep the
+// kernel defaults, l3 and bridge, and are None when read back with an unknown value.
+#[derive(Debug, Default)]
+pub struct Ipvlan {
+    pub mode: Option<IpvlanMode>,
+    pub flag: Option<IpvlanFlag>,
+}
+
+pub type Ipvtap = Ipvlan;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BondMode {
+    BalanceRr,
+    ActiveBackup,
+    BalanceXor,
+    Broadcast,
+    Ieee8023Ad,
+    BalanceTlb,
+    BalanceAlb,
+}
+
+impl From<BondMode> for u8 {
+    fn from(mode: BondMode) -> Self {
+        match mode {
+            BondMode::BalanceRr => BOND_MODE_ROUNDROBIN,
+            BondMode::ActiveBackup => BOND_MODE_ACTIVEBACKUP,
+            BondMode::BalanceXor => BOND_MODE_XOR,
+            BondMode::Broadcast => BOND_MODE_BROADCAST,
+            BondMode::Ieee8023Ad => BOND_MODE_8023AD,
+            BondMode::BalanceTlb => BOND_MODE_TLB,
+            BondMode::BalanceAlb => BOND_MODE_ALB,
+        }
+    }
+}
+
+impl TryFrom<u8> for BondMode {
+    type Error = anyhow::Error;
+
+    fn try_from(mode: u8) -> Result<Self, Self::Error> {
+        match mode {
+            BOND_MODE_ROUNDROBIN => Ok(BondMode::BalanceRr),
+            BOND_MODE_ACTIVEBACKUP => Ok(BondMode::ActiveBackup),
+            BOND_MODE_XOR => Ok(BondMode::BalanceXor),
+            BOND_MODE_BROADCAST => Ok(BondMode::Broadcast),
+            BOND_MODE_8023AD => Ok(BondMode::Ieee8023Ad),
+            BOND_MODE_TLB => Ok(BondMode::BalanceTlb),
+            BOND_MODE_ALB => Ok(BondMode::BalanceAlb),
+            _ => bail!("invalid bond mode: {}", mode),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BondXmitHashPolicy {
+    Layer2,
+    Layer34,
+    Layer23,
+    Encap23,
+    Encap34,
+    VlanSrcMac,
+}
+
+impl From<BondXmitHashPolicy> for u8 {
+    fn from(policy: BondXmitHashPolicy) -> Self {
+        match policy {
+            BondXmitHashPolicy::Layer2 => BOND_XMIT_POLICY_LAYER2,
+            BondXmitHashPolicy::Layer34 => BOND_XMIT_POLICY_LAYER34,
+            BondXmitHashPolicy::Layer23 => BOND_XMIT_POLICY_LAYER23,
+            BondXmitHashPolicy::Encap23 => BOND_XMIT_POLICY_ENCAP23,
+            BondXmitHashPolicy::Encap34 => BOND_XMIT_POLICY_ENCAP34,
+            BondXmitHashPolicy::VlanSrcMac => BOND_XMIT_POLICY_VLAN_SRCMAC,
+        }
+    }
+}
+
+impl TryFrom<u8> for BondXmitHashPolicy {
+    type Error = anyhow::Error;
+
+    fn try_from(policy: u8) -> Result<Self, Self::Error> {
+        match policy {
+            BOND_XMIT_POLICY_LAYER2 => Ok(BondXmitHashPolicy::Layer2),
+            BOND_XMIT_POLICY_LAYER34 => Ok(BondXmitHashPolicy::Layer34),
+            BOND_XMIT_POLICY_LAYER23 => Ok(BondXmitHashPolicy::Layer23),
+            BOND_XMIT_POLICY_ENCAP23 => Ok(BondXmitHashPolicy::Encap23),
+            BOND_XMIT_POLICY_ENCAP34 => Ok(BondXmitHashPolicy::Encap34),
+            BOND_XMIT_POLICY_VLAN_SRCMAC => Ok(BondXmitHashPolicy::VlanSrcMac),
+            _ => bail!("invalid bond xmit hash policy: {}", policy),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BondLacpRate {
+    Slow,
+    Fast,
+}
+
+impl From<BondLacpRate> for u8 {
+    fn from(rate: BondLacpRate) -> Self {
+        match rate {
+            BondLacpRate::Slow => AD_LACP_SLOW,
+            BondLacpRate::Fast => AD_LACP_FAST,
+        }
+    }
+}
+
+impl TryFrom<u8> for BondLacpRate {
+    type Error = anyhow::Error;
+
+    fn try_from(rate: u8) -> Result<Self, Self::Error> {
+        match rate {
+            AD_LACP_SLOW => Ok(BondLacpRate::Slow),
+            AD_LACP_FAST => Ok(BondLacpRate::Fast),
+            _ => bail!("invalid bond lacp rate: {}", rate),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BondAdSelect {
+    Stable,
+    Bandwidth,
+    Count,
+}
+
+impl From<BondAdSelect> for u8 {
+    fn from(select: BondAdSelect) -> Self {
+        match select {
+            BondAdSelect::Stable => BOND_AD_STABLE,
+            BondAdSelect::Bandwidth => BOND_AD_BANDWIDTH,
+            BondAdSelect::Count => BOND_AD_COUNT,
+        }
+    }
+}
+
+impl TryFrom<u8> for BondAdSelect {
+    type Error = anyhow::Error;
+
+    fn try_from(select: u8) -> Result<Self, Self::Error> {
+        match select {
+            BOND_AD_STABLE => Ok(BondAdSelect::Stable),
+            BOND_AD_BANDWIDTH => Ok(BondAdSelect::Bandwidth),
+            BOND_AD_COUNT => Ok(BondAdSelect::Count),
+            _ => bail!("invalid bond ad select: {}", select),
+        }
+    }
+}
+
+// 802.3ad aggregator info of a bond, read only
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BondAdInfo {
+    pub aggregator_id: u16,
+    pub num_ports: u16,
+    pub actor_key: u16,
+    pub partner_key: u16,
+    pub partner_mac: MacAddr6,
+}
+
+// options left as None keep the kernel default, slaves are added by link_set_master
+#[derive(Debug, Default)]
+pub struct Bond {
+    pub mode: Option<BondMode>,
+    // index of the active slave in active-backup, alb and tlb modes
+    pub active_slave: Option<u32>,
+    pub miimon: Option<u32>,
+    pub up_delay: Option<u32>,
+    pub down_delay: Option<u32>,
+    pub peer_notify_delay: Option<u32>,
+    pub use_carrier: Option<bool>,
+    pub arp_interval: Option<u32>,
+    pub arp_ip_targets: Vec<Ipv4Addr>,
+    pub ns_ip6_targets: Vec<Ipv6Addr>,
+    // BOND_ARP_VALIDATE_*: none, active, backup, all, filter...
+    pub arp_validate: Option<u32>,
+    // 0: any, 1: all
+    pub arp_all_targets: Option<u32>,
+    // index of the primary slave
+    pub primary: Option<u32>,
+    // 0: always, 1: better, 2: failure
+    pub primary_reselect: Option<u8>,
+    // 0: none, 1: active, 2: follow
+    pub fail_over_mac: Option<u8>,
+    pub xmit_hash_policy: Option<BondXmitHashPolicy>,
+    pub resend_igmp: Option<u32>,
+    pub num_peer_notif: Option<u8>,
+    pub all_slaves_active: Option<bool>,
+    pub min_links: Option<u32>,
+    pub lp_interval: Option<u32>,
+    pub packets_per_slave: Option<u32>,
+    pub lacp_active: Option<bool>,
+    pub lacp_rate: Option<BondLacpRate>,
+    pub ad_select: Option<BondAdSelect>,
+    pub ad_actor_sys_prio: Option<u16>,
+    pub ad_user_port_key: Option<u16>,
+    pub ad_actor_system: Option<MacAddr6>,
+    pub tlb_dynamic_lb: Option<bool>,
+    pub missed_max: Option<u8>,
+    pub ad_info: Option<BondAdInfo>,
+}
+
+// IFLA_INFO_SLAVE_DATA of a bond slave, read only
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BondSlave {
+    pub state: Option<BondPortState>,
+    pub mii_status: Option<MiiStatus>,
+    pub link_failure_count: u32,
+    pub perm_hardware_addr: Option<MacAddr6>,
+    pub queue_id: u16,
+    pub prio: i32,
+    pub aggregator_id: Option<u16>,
+    pub ad_actor_oper_port_state: Option<u8>,
+    pub ad_partner_oper_port_state: Option<u16>,
+}
+
+// the stp state of a bridge port
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BridgePortState {
+    #[default]
+    Disabled,
+    Listening,
+    Learning,
+    Forwarding,
+    Blocking,
+}
+
+impl From<BridgePortState> for u8 {
+    fn from(state: BridgePortState) -> Self {
+        match state {
+            BridgePortState::Disabled => BR_STATE_DISABLED,
+            BridgePortState::Listening => BR_STATE_LISTENING,
+            BridgePortState::Learning => BR_STATE_LEARNING,
+            BridgePortState::Forwarding => BR_STATE_FORWARDING,
+            BridgePortState::Blocking => BR_STATE_BLOCKING,
+        }
+    }
 }
 
+impl TryFrom<u8> for BridgePortState {
+    type Error = anyhow::Error;
+
+    fn try_from(state: u8) -> Result<Self, Self::Error> {
+        match state {
+            BR_STATE_DISABLED => Ok(BridgePortState::Disabled),
+            BR_STATE_LISTENING => Ok(BridgePortState::Listening),
+            BR_STATE_LEARNING => Ok(BridgePortState::Learning),
+            BR_STATE_FORWARDING => Ok(BridgePortState::Forwarding),
+            BR_STATE_BLOCKING => Ok(BridgePortState::Blocking),
+            _ => bail!("invalid bridge port state: {}", state),
+        }
+    }
+}
+
+// IFLA_INFO_SLAVE_DATA / IFLA_PROTINFO of a bridge port, unset fields are left untouched
+// by link_set_bridge_slave
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BridgeSlave {
+    pub state: Option<BridgePortState>,
+    pub priority: Option<u16>,
+    pub cost: Option<u32>,
+    // hairpin mode, reflect frames back out of the port they came in
+    pub hairpin: Option<bool>,
+    // bpdu guard
+    pub guard: Option<bool>,
+    // root port protection
+    pub root_block: Option<bool>,
+    pub fast_leave: Option<bool>,
+    pub learning: Option<bool>,
+    pub unicast_flood: Option<bool>,
+    pub multicast_flood: Option<bool>,
+    pub broadcast_flood: Option<bool>,
+    pub proxy_arp: Option<bool>,
+    pub proxy_arp_wifi: Option<bool>,
+    pub neigh_suppress: Option<bool>,
+    pub isolated: Option<bool>,
+    pub locked: Option<bool>,
+    // mac authentication bypass, requires locked
+    pub mab: Option<bool>,
+    // map vlans to vnis on a vxlan port in external mode, see bridge::bridge_vlan_tunnel_add
+    pub vlan_tunnel: Option<bool>,
+}
+
+// the slave data of a link enslaved to a master device
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkSlave {
+    Bond(BondSlave),
+    Bridge(BridgeSlave),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TunnelEncapType {
+    #[default]
+    None,
+    Fou,
+    Gue,
+    // an encap type this crate doesn't know, kept as its TUNNEL_ENCAP_* value
+    Other(u16),
+}
+
+impl From<TunnelEncapType> for u16 {
+    fn from(encap_type: TunnelEncapType) -> Self {
+        match encap_type {
+            TunnelEncapType::None => TUNNEL_ENCAP_NONE,
+            TunnelEncapType::Fou => TUNNEL_ENCAP_FOU,
+            TunnelEncapType::Gue => TUNNEL_ENCAP_GUE,
+            TunnelEncapType::Other(encap_type) => encap_type,
+        }
+    }
+}
+
+impl From<u16> for TunnelEncapType {
+    fn from(encap_type: u16) -> Self {
+        match encap_type {
+            TUNNEL_ENCAP_NONE => TunnelEncapType::None,
+            TUNNEL_ENCAP_FOU => TunnelEncapType::Fou,
+            TUNNEL_ENCAP_GUE => TunnelEncapType::Gue,
+            _ => TunnelEncapType::Other(encap_type),
+        }
+    }
+}
+
+// udp encapsulation of a tunnel, `ip link ... encap fou encap-sport $sport encap-dport $dport`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TunnelEncap {
+    pub encap_type: TunnelEncapType,
+    // TUNNEL_ENCAP_FLAG_*
+    pub flags: u16,
+    // 0 lets the kernel pick the source port
+    pub sport: u16,
+    pub dport: u16,
+}
+
+// Gre configures gre, gretap, ip6gre, ip6gretap and erspan links.
+// For the ip6 kinds `ttl` is the hop limit and `tos` the traffic class.
+#[derive(Debug, Default)]
+pub struct Gre {
+    // index of the underlying device
+    pub link: u32,
+    pub local: Option<IpAddr>,
+    pub remote: Option<IpAddr>,
+    pub ttl: u8,
+    pub tos: u8,
+    // ip6 kinds only
+    pub flow_label: u32,
+    pub encap_limit: Option<u8>,
+    // IP6_TNL_F_* flags of the ip6 kinds
+    pub flags: u32,
+    // GRE_* flags, GRE_KEY is added when a key is set
+    pub iflags: u16,
+    pub oflags: u16,
+    pub ikey: u32,
+    pub okey: u32,
+    pub pmtudisc: Option<bool>,
+    pub ignore_df: Option<bool>,
+    pub fwmark: u32,
+    pub encap: Option<TunnelEncap>,
+    // external mode, `ip link ... external`
+    pub collect_metadata: bool,
+    // erspan only
+    pub erspan_ver: Option<u8>,
+    pub erspan_index: Option<u32>,
+    // 0: ingress, 1: egress
+    pub erspan_dir: Option<u8>,
+    pub erspan_hwid: Option<u16>,
+}
+
+// Iptun configures ipip, sit and ip6tnl links.
+// For ip6tnl `ttl` is the hop limit and `tos` the traffic class.
+#[derive(Debug, Default)]
+pub struct Iptun {
+    // index of the underlying device
+    pub link: u32,
+    pub local: Option<IpAddr>,
+    pub remote: Option<IpAddr>,
+    pub ttl: u8,
+    pub tos: u8,
+    // ip6tnl only
+    pub flow_label: u32,
+    pub encap_limit: Option<u8>,
+    // SIT_ISATAP for sit, IP6_TNL_F_* for ip6tnl
+    pub flags: u32,
+    // IPPROTO_* of the inner packets, 0 accepts any
+    pub proto: Option<u8>,
+    pub pmtudisc: Option<bool>,
+    pub fwmark: u32,
+    pub encap: Option<TunnelEncap>,
+    pub collect_metadata: bool,
+}
 
 #[derive(Debug, Default)]
 pub struct Device {}
 
+// IPv6 link-local address generation mode, `ip link set $link addrgenmode $mode`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddrGenMode {
+    Eui64,
+    None,
+    StableSecret,
+    Random,
+}
+
+impl From<AddrGenMode> for u8 {
+    fn from(mode: AddrGenMode) -> Self {
+        match mode {
+            AddrGenMode::Eui64 => IN6_ADDR_GEN_MODE_EUI64,
+            AddrGenMode::None => IN6_ADDR_GEN_MODE_NONE,
+            AddrGenMode::StableSecret => IN6_ADDR_GEN_MODE_STABLE_PRIVACY,
+            AddrGenMode::Random => IN6_ADDR_GEN_MODE_RANDOM,
+        }
+    }
+}
+
+impl TryFrom<u8> for AddrGenMode {
+    type Error = anyhow::Error;
+
+    fn try_from(mode: u8) -> Result<Self, Self::Error> {
+        match mode {
+            IN6_ADDR_GEN_MODE_EUI64 => Ok(AddrGenMode::Eui64),
+            IN6_ADDR_GEN_MODE_NONE => Ok(AddrGenMode::None),
+            IN6_ADDR_GEN_MODE_STABLE_PRIVACY => Ok(AddrGenMode::StableSecret),
+            IN6_ADDR_GEN_MODE_RANDOM => Ok(AddrGenMode::Random),
+            _ => bail!("invalid addr gen mode: {}", mode),
+        }
+    }
+}
+
+// IFLA_AF_SPEC/AF_INET attributes of a link
+#[derive(Debug, Default)]
+pub struct LinkInet {
+    pub devconf: Option<InetDevConf>,
+}
+
+// IFLA_AF_SPEC/AF_INET6 attributes of a link
+#[derive(Debug, Default)]
+pub struct LinkInet6 {
+    pub flags: u32,
+    pub token: Option<Ipv6Addr>,
+    pub addr_gen_mode: Option<AddrGenMode>,
+    pub devconf: Option<Inet6DevConf>,
+}
+
 pub type Family = u8;
 
 pub const FAMILY_ALL: u8 = AF_UNSPEC as u8;
 pub const FAMILY_V4: u8 = AF_INET as u8;
 pub const FAMILY_V6: u8 = AF_INET6 as u8;
+pub const FAMILY_BRIDGE: u8 = AF_BRIDGE as u8;
@@ -9,9 +9,9 @@ use netlink_packet_route::route::Nla;
 
 pub use constants::*;
 
-use crate::{LinkIndex, unwrap_enum, utils};
+use crate::{Link, LinkIndex, LinkKind, unwrap_enum, utils};
 use crate::handle::NetlinkHandle;
-use crate::nl_type::Family;
+use crate::nl_type::{Family, Vrf};
 use crate::types::{Route, RouteProtocol};
 use crate::utils::bytes_to_ip;
 
@@ -247,12 +247,28 @@ pub fn route_list_filtered(family: Family, route_filter: Option<Route>, filter_m
             if filter_mask & RT_FILTER_OIF != 0 && route.link_index != filter.link_index {
                 continue;
             }
+            if filter_mask & RT_FILTER_TABLE != 0 && filter.table.is_some_and(|table| table != RT_TABLE_UNSPEC as u32) && route.table != filter.table {
+                continue;
+            }
         }
         routes.push(route);
     }
     Ok(routes)
 }
 
+// route_list_vrf returns the routes in the table of a vrf.
+// Equivalent to: `ip route show vrf $vrf`
+pub fn route_list_vrf(vrf: &Link, family: Family) -> anyhow::Result<Vec<Route>> {
+    let LinkKind::Vrf(Vrf { table }) = &vrf.link_kind else {
+        bail!("link {} is not a vrf", vrf.link_attrs.name);
+    };
+    let filter = Route {
+        table: Some(*table),
+        ..Default::default()
+    };
+    route_list_filtered(family, Some(filter), RT_FILTER_TABLE)
+}
+
 fn msg_to_route(msg: RtnlMessage) -> anyhow::Result<Route> {
     let msg: Option<RouteMessage> = unwrap_enum!(msg, RtnlMessage::NewRoute);
     let msg: RouteMessage = msg.ok_or(anyhow!("msg is not new route"))?;
@@ -304,6 +320,7 @@ mod tests {
 
     use ipnetwork::{IpNetwork, Ipv4Network};
     use log::info;
+    use netlink_packet_route::RT_TABLE_LOCAL;
 
     use crate::{link_by_name, TryAsLinkIndex};
     use crate::nl_type::FAMILY_V4;
@@ -340,4 +357,16 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_route_list_table() -> anyhow::Result<()> {
+        let filter = Route {
+            table: Some(RT_TABLE_LOCAL as u32),
+            ..Default::default()
+        };
+        let routes = route_list_filtered(FAMILY_V4, Some(filter), RT_FILTER_TABLE)?;
+        assert!(!routes.is_empty());
+        assert!(routes.iter().all(|r| r.table == Some(RT_TABLE_LOCAL as u32)));
+        Ok(())
+    }
 }
@@ -0,0 +1,89 @@
+use netlink_packet_core::{NetlinkDeserializable, NetlinkHeader, NetlinkPayload, NetlinkSerializable};
+use netlink_packet_utils::nla::{self, NlaBuffer, NlasIterator};
+use netlink_packet_utils::{DecodeError, Emitable, Parseable};
+
+use crate::nl_type::Family;
+
+// RtnlHeader is the fixed size struct in front of the nlas of a rtnetlink message
+pub trait RtnlHeader: Sized {
+    // length of the header including padding
+    const LEN: usize;
+
+    // emit the header into a zeroed buffer of LEN bytes
+    fn emit(&self, buffer: &mut [u8]);
+
+    // parse the header from the start of a message payload
+    fn parse(payload: &[u8]) -> Result<Self, DecodeError>;
+}
+
+// RtnlExtMessage is a rtnetlink message that RtnlMessage does not support,
+// `message_type` is one of the RTM_* values of its family
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct RtnlExtMessage<H, T> {
+    pub message_type: u16,
+    pub header: H,
+    pub nlas: Vec<T>,
+}
+
+impl<H: RtnlHeader, T: nla::Nla> NetlinkSerializable for RtnlExtMessage<H, T> {
+    fn message_type(&self) -> u16 {
+        self.message_type
+    }
+
+    fn buffer_len(&self) -> usize {
+        H::LEN + self.nlas.as_slice().buffer_len()
+    }
+
+    fn serialize(&self, buffer: &mut [u8]) {
+        buffer[..H::LEN].fill(0);
+        self.header.emit(&mut buffer[..H::LEN]);
+        self.nlas.as_slice().emit(&mut buffer[H::LEN..]);
+    }
+}
+
+impl<H, T> NetlinkDeserializable for RtnlExtMessage<H, T>
+    where H: RtnlHeader, T: for<'a> Parseable<NlaBuffer<&'a [u8]>> {
+    type Error = DecodeError;
+
+    fn deserialize(header: &NetlinkHeader, payload: &[u8]) -> Result<Self, Self::Error> {
+        let mut msg = RtnlExtMessage {
+            message_type: header.message_type,
+            header: H::parse(payload)?,
+            nlas: vec![],
+        };
+        if payload.len() > H::LEN {
+            for nla in NlasIterator::new(&payload[H::LEN..]) {
+                msg.nlas.push(T::parse(&nla?)?);
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<H, T> From<RtnlExtMessage<H, T>> for NetlinkPayload<RtnlExtMessage<H, T>> {
+    fn from(msg: RtnlExtMessage<H, T>) -> Self {
+        NetlinkPayload::InnerMessage(msg)
+    }
+}
+
+// FamilyHeader is a header holding only the family byte, padded to 4 bytes,
+// like struct netconfmsg and struct ndtmsg
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct FamilyHeader {
+    pub family: Family,
+}
+
+impl RtnlHeader for FamilyHeader {
+    const LEN: usize = 4;
+
+    fn emit(&self, buffer: &mut [u8]) {
+        buffer[0] = self.family;
+    }
+
+    fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        match payload.first() {
+            Some(family) => Ok(FamilyHeader { family: *family }),
+            None => Err(DecodeError::from("rtnetlink message too short")),
+        }
+    }
+}
@@ -0,0 +1,317 @@
+use std::net::IpAddr;
+
+use anyhow::{anyhow, Context};
+use netlink_packet_core::{NLM_F_ACK, NLM_F_CREATE, NLM_F_DUMP};
+use netlink_packet_utils::nla::{self, DefaultNla, NlaBuffer, NlasIterator};
+use netlink_packet_utils::parsers::parse_u32;
+use netlink_packet_utils::{DecodeError, Emitable, Parseable};
+
+use crate::handle::NetlinkHandle;
+use crate::nl_linux::{RTM_DELTUNNEL, RTM_GETTUNNEL, RTM_NEWTUNNEL, VXLAN_VNIFILTER_ENTRY, VXLAN_VNIFILTER_ENTRY_END, VXLAN_VNIFILTER_ENTRY_GROUP, VXLAN_VNIFILTER_ENTRY_GROUP6, VXLAN_VNIFILTER_ENTRY_START};
+use crate::nl_type::{Family, FAMILY_BRIDGE};
+use crate::rtnl::{RtnlExtMessage, RtnlHeader};
+use crate::utils;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum VniFilterEntryNla {
+    Start(u32),
+    End(u32),
+    Group(Vec<u8>),
+    Group6(Vec<u8>),
+    Other(DefaultNla),
+}
+
+impl nla::Nla for VniFilterEntryNla {
+    fn value_len(&self) -> usize {
+        match self {
+            VniFilterEntryNla::Start(_) | VniFilterEntryNla::End(_) => 4,
+            VniFilterEntryNla::Group(bytes) | VniFilterEntryNla::Group6(bytes) => bytes.len(),
+            VniFilterEntryNla::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            VniFilterEntryNla::Start(_) => VXLAN_VNIFILTER_ENTRY_START,
+            VniFilterEntryNla::End(_) => VXLAN_VNIFILTER_ENTRY_END,
+            VniFilterEntryNla::Group(_) => VXLAN_VNIFILTER_ENTRY_GROUP,
+            VniFilterEntryNla::Group6(_) => VXLAN_VNIFILTER_ENTRY_GROUP6,
+            VniFilterEntryNla::Other(attr) => attr.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            VniFilterEntryNla::Start(v) | VniFilterEntryNla::End(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            VniFilterEntryNla::Group(bytes) | VniFilterEntryNla::Group6(bytes) => buffer.copy_from_slice(bytes),
+            VniFilterEntryNla::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for VniFilterEntryNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            VXLAN_VNIFILTER_ENTRY_START => VniFilterEntryNla::Start(parse_u32(payload).context("invalid VXLAN_VNIFILTER_ENTRY_START value")?),
+            VXLAN_VNIFILTER_ENTRY_END => VniFilterEntryNla::End(parse_u32(payload).context("invalid VXLAN_VNIFILTER_ENTRY_END value")?),
+            VXLAN_VNIFILTER_ENTRY_GROUP => VniFilterEntryNla::Group(payload.to_vec()),
+            VXLAN_VNIFILTER_ENTRY_GROUP6 => VniFilterEntryNla::Group6(payload.to_vec()),
+            _ => VniFilterEntryNla::Other(DefaultNla::parse(buf).context("invalid VXLAN_VNIFILTER_ENTRY nla")?),
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum TunnelNla {
+    VniFilterEntry(Vec<VniFilterEntryNla>),
+    Other(DefaultNla),
+}
+
+impl nla::Nla for TunnelNla {
+    fn value_len(&self) -> usize {
+        match self {
+            TunnelNla::VniFilterEntry(nlas) => nlas.as_slice().buffer_len(),
+            TunnelNla::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            TunnelNla::VniFilterEntry(_) => VXLAN_VNIFILTER_ENTRY,
+            TunnelNla::Other(attr) => attr.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            TunnelNla::VniFilterEntry(nlas) => nlas.as_slice().emit(buffer),
+            TunnelNla::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn is_nested(&self) -> bool {
+        matches!(self, TunnelNla::VniFilterEntry(_))
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for TunnelNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        Ok(match buf.kind() {
+            VXLAN_VNIFILTER_ENTRY => {
+                let mut nlas = vec![];
+                for nla in NlasIterator::new(buf.value()) {
+                    nlas.push(VniFilterEntryNla::parse(&nla?)?);
+                }
+                TunnelNla::VniFilterEntry(nlas)
+            }
+            _ => TunnelNla::Other(DefaultNla::parse(buf).context("invalid tunnel nla")?),
+        })
+    }
+}
+
+// TunnelHeader is struct tunnel_msg: family, flags, 2 reserved bytes and the ifindex
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct TunnelHeader {
+    pub family: Family,
+    pub flags: u8,
+    pub ifindex: u32,
+}
+
+impl RtnlHeader for TunnelHeader {
+    const LEN: usize = 8;
+
+    fn emit(&self, buffer: &mut [u8]) {
+        buffer[0] = self.family;
+        buffer[1] = self.flags;
+        buffer[4..8].copy_from_slice(&self.ifindex.to_ne_bytes());
+    }
+
+    fn parse(payload: &[u8]) -> Result<Self, DecodeError> {
+        if payload.len() < Self::LEN {
+            return Err(DecodeError::from("tunnel message too short"));
+        }
+        Ok(TunnelHeader {
+            family: payload[0],
+            flags: payload[1],
+            ifindex: parse_u32(&payload[4..8])?,
+        })
+    }
+}
+
+// TunnelMessage is a RTM_NEWTUNNEL/RTM_DELTUNNEL/RTM_GETTUNNEL message
+pub type TunnelMessage = RtnlExtMessage<TunnelHeader, TunnelNla>;
+
+// VniFilter is a range of vnis served by a vxlan device in vnifilter mode,
+// `vni_end` equals `vni_start` for a single vni.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VniFilter {
+    pub link_index: u32,
+    pub vni_start: u32,
+    pub vni_end: u32,
+    // multicast group, which needs a local interface, or unicast remote of the vnis
+    pub group: Option<IpAddr>,
+}
+
+impl VniFilter {
+    fn entry_nlas(&self) -> Vec<VniFilterEntryNla> {
+        let mut nlas = vec![VniFilterEntryNla::Start(self.vni_start)];
+        if self.vni_end > self.vni_start {
+            nlas.push(VniFilterEntryNla::End(self.vni_end));
+        }
+        match &self.group {
+            Some(group @ IpAddr::V4(_)) => nlas.push(VniFilterEntryNla::Group(utils::ip_to_bytes(group))),
+            Some(group @ IpAddr::V6(_)) => nlas.push(VniFilterEntryNla::Group6(utils::ip_to_bytes(group))),
+            None => {}
+        }
+        nlas
+    }
+
+    fn from_entry_nlas(link_index: u32, nlas: &[VniFilterEntryNla]) -> Self {
+        let mut filter = VniFilter {
+            link_index,
+            ..Default::default()
+        };
+        for nla in nlas {
+            match nla {
+                VniFilterEntryNla::Start(v) => filter.vni_start = *v,
+                VniFilterEntryNla::End(v) => filter.vni_end = *v,
+                VniFilterEntryNla::Group(bytes) | VniFilterEntryNla::Group6(bytes) => {
+                    filter.group = utils::bytes_to_ip_by_len(bytes).ok().filter(|ip| !ip.is_unspecified());
+                }
+                _ => {}
+            }
+        }
+        filter.vni_end = filter.vni_end.max(filter.vni_start);
+        filter
+    }
+}
+
+impl TryFrom<&TunnelMessage> for Vec<VniFilter> {
+    type Error = anyhow::Error;
+
+    fn try_from(msg: &TunnelMessage) -> Result<Self, Self::Error> {
+        if msg.message_type != RTM_NEWTUNNEL {
+            return Err(anyhow!("msg is not new tunnel"));
+        }
+        Ok(msg.nlas.iter()
+            .filter_map(|nla| crate::unwrap_enum!(nla, TunnelNla::VniFilterEntry))
+            .map(|nlas| VniFilter::from_entry_nlas(msg.header.ifindex, nlas))
+            .collect())
+    }
+}
+
+// vni filters are handled by the bridge family
+fn vni_filter_msg(message_type: u16, filter: &VniFilter) -> TunnelMessage {
+    TunnelMessage {
+        message_type,
+        header: TunnelHeader {
+            family: FAMILY_BRIDGE,
+            flags: 0,
+            ifindex: filter.link_index,
+        },
+        nlas: vec![TunnelNla::VniFilterEntry(filter.entry_nlas())],
+    }
+}
+
+// vxlan_vni_add adds a range of vnis to a vxlan device created with `vni_filter`,
+// adding existing vnis updates their group.
+// Equivalent to: `bridge vni add dev $link vni $start-$end group $group`
+pub fn vxlan_vni_add(filter: &VniFilter) -> anyhow::Result<()> {
+    let msg = vni_filter_msg(RTM_NEWTUNNEL, filter);
+    NetlinkHandle::new().execute(msg, NLM_F_CREATE | NLM_F_ACK)?;
+    Ok(())
+}
+
+// vxlan_vni_del deletes a range of vnis from a vxlan device, the group is ignored.
+// Equivalent to: `bridge vni del dev $link vni $start-$end`
+pub fn vxlan_vni_del(filter: &VniFilter) -> anyhow::Result<()> {
+    let msg = vni_filter_msg(RTM_DELTUNNEL, filter);
+    NetlinkHandle::new().execute(msg, NLM_F_ACK)?;
+    Ok(())
+}
+
+// vxlan_vni_list returns the vni ranges of a vxlan device, or of every vxlan device in
+// vnifilter mode when `link_index` is 0. Consecutive vnis sharing a group are merged.
+// Equivalent to: `bridge vni show dev $link`
+pub fn vxlan_vni_list(link_index: u32) -> anyhow::Result<Vec<VniFilter>> {
+    let msg = TunnelMessage {
+        message_type: RTM_GETTUNNEL,
+        header: TunnelHeader {
+            family: FAMILY_BRIDGE,
+            ifindex: link_index,
+            ..Default::default()
+        },
+        nlas: vec![],
+    };
+    let resp = NetlinkHandle::new().execute(msg, NLM_F_DUMP | NLM_F_ACK)?;
+    let mut filters = vec![];
+    for msg in &resp {
+        filters.extend(Vec::<VniFilter>::try_from(msg)?);
+    }
+    Ok(filters)
+}
+
+#[cfg(test)]
+mod tests {
+    use netlink_packet_core::{NetlinkDeserializable, NetlinkHeader, NetlinkSerializable};
+
+    use crate::nl_type::Vxlan;
+    use crate::{link_add, link_by_name, link_del, Link, LinkAttrs, LinkKind};
+
+    use super::*;
+
+    #[test]
+    fn test_tunnel_parse() -> anyhow::Result<()> {
+        let filter = VniFilter {
+            link_index: 7,
+            vni_start: 100,
+            vni_end: 110,
+            group: Some("239.1.1.1".parse()?),
+        };
+        let mut msg = vni_filter_msg(RTM_NEWTUNNEL, &filter);
+        msg.nlas.push(TunnelNla::VniFilterEntry(vec![VniFilterEntryNla::Start(200)]));
+        let mut buf = vec![0u8; msg.buffer_len()];
+        msg.serialize(&mut buf);
+        let mut header = NetlinkHeader::default();
+        header.message_type = RTM_NEWTUNNEL;
+        let parsed = TunnelMessage::deserialize(&header, &buf)?;
+        assert_eq!(parsed, msg);
+        let filters = Vec::<VniFilter>::try_from(&parsed)?;
+        assert_eq!(filters, vec![filter, VniFilter { link_index: 7, vni_start: 200, vni_end: 200, group: None }]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_vxlan_vni_add_del_list() -> anyhow::Result<()> {
+        let link = Link {
+            link_attrs: LinkAttrs {
+                name: "vxlan240".to_string(),
+                ..Default::default()
+            },
+            link_kind: LinkKind::Vxlan(Vxlan {
+                flow_based: true,
+                vni_filter: true,
+                port: 4791,
+                ..Default::default()
+            }),
+        };
+        link_add(&link)?;
+        let link_index = link_by_name("vxlan240")?.unwrap().link_attrs.index;
+
+        let result = (|| {
+            vxlan_vni_add(&VniFilter { link_index, vni_start: 100, vni_end: 110, group: Some("10.0.0.1".parse()?) })?;
+            vxlan_vni_add(&VniFilter { link_index, vni_start: 200, vni_end: 200, group: None })?;
+            vxlan_vni_del(&VniFilter { link_index, vni_start: 105, vni_end: 110, group: None })?;
+            vxlan_vni_list(link_index)
+        })();
+        link_del(link_index)?;
+
+        let filters = result?;
+        assert_eq!(filters, vec![
+            VniFilter { link_index, vni_start: 100, vni_end: 104, group: Some("10.0.0.1".parse()?) },
+            VniFilter { link_index, vni_start: 200, vni_end: 200, group: None },
+        ]);
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use std::fs::{File, OpenOptions};
+use std::os::fd::AsRawFd;
+
+use anyhow::bail;
+use netlink_packet_utils::nla::{DefaultNla, Nla};
+
+use crate::{Link, LinkKind, link_by_name, link_set_master_by_index, utils};
+use crate::nl_linux::{IFLA_TUN_GROUP, IFLA_TUN_MULTI_QUEUE, IFLA_TUN_NUM_DISABLED_QUEUES, IFLA_TUN_NUM_QUEUES, IFLA_TUN_OWNER, IFLA_TUN_PERSIST, IFLA_TUN_PI, IFLA_TUN_TYPE, IFLA_TUN_VNET_HDR, TUNSETGROUP, TUNSETIFF, TUNSETOWNER, TUNSETPERSIST};
+use crate::nl_type::{Tuntap, TUNTAP_DEFAULTS, TUNTAP_MODE_TAP, TUNTAP_MODE_TUN, TUNTAP_MULTI_QUEUE, TUNTAP_MULTI_QUEUE_DEFAULTS, TUNTAP_NO_PI, TUNTAP_VNET_HDR};
+
+const TUN_PATH: &str = "/dev/net/tun";
+const IFNAMSIZ: usize = 16;
+
+// struct ifreq as used by TUNSETIFF: the name followed by the flags
+#[repr(C)]
+struct IfReq {
+    name: [u8; IFNAMSIZ],
+    flags: u16,
+    _pad: [u8; 22],
+}
+
+// tuntap_add creates a tun/tap device over /dev/net/tun, netlink can't create them.
+// The name of the link is updated to the one picked by the kernel, e.g. for "tap%d",
+// and the fds of the queues are stored in Tuntap::fds.
+// Equivalent to: `ip tuntap add $name mode $mode`
+pub fn tuntap_add(link: &mut Link) -> anyhow::Result<()> {
+    let LinkKind::Tuntap(tuntap) = &mut link.link_kind else {
+        bail!("link {} is not a tuntap", link.link_attrs.name);
+    };
+    if tuntap.mode != TUNTAP_MODE_TUN && tuntap.mode != TUNTAP_MODE_TAP {
+        bail!("unknown tuntap mode: {}", tuntap.mode);
+    }
+    let name = link.link_attrs.name.as_bytes();
+    if name.len() >= IFNAMSIZ {
+        bail!("invalid tuntap name: {}", link.link_attrs.name);
+    }
+
+    let mut req = IfReq {
+        name: [0; IFNAMSIZ],
+        flags: tuntap.flags,
+        _pad: [0; 22],
+    };
+    req.name[..name.len()].copy_from_slice(name);
+    // legacy single queue device
+    let queues = if tuntap.queues <= 0 { 1 } else { tuntap.queues };
+    if tuntap.flags == 0 {
+        req.flags = if tuntap.queues <= 0 { TUNTAP_DEFAULTS } else { TUNTAP_MULTI_QUEUE_DEFAULTS };
+    }
+    req.flags |= tuntap.mode;
+
+    let mut fds = Vec::with_capacity(queues as usize);
+    for i in 0..queues {
+        let file = OpenOptions::new().read(true).write(true).open(TUN_PATH)?;
+        tun_ioctl(&file, TUNSETIFF, &mut req as *mut IfReq as libc::c_ulong)?;
+        // the kernel fills in the name if it was empty or a template
+        if i == 0 {
+            let len = req.name.iter().position(|b| *b == 0).unwrap_or(IFNAMSIZ);
+            link.link_attrs.name = String::from_utf8_lossy(&req.name[..len]).to_string();
+        }
+        fds.push(file);
+    }
+
+    if let Some(owner) = tuntap.owner {
+        tun_ioctl(&fds[0], TUNSETOWNER, owner as libc::c_ulong)?;
+    }
+    if let Some(group) = tuntap.group {
+        tun_ioctl(&fds[0], TUNSETGROUP, group as libc::c_ulong)?;
+    }
+    if !tuntap.non_persist {
+        tun_ioctl(&fds[0], TUNSETPERSIST, 1)?;
+    }
+    tuntap.fds = fds;
+
+    if let Some(found) = link_by_name(&link.link_attrs.name)? {
+        link.link_attrs.index = found.link_attrs.index;
+    }
+    // can't set the master during creation
+    if link.link_attrs.master_index != 0 {
+        link_set_master_by_index(link, link.link_attrs.master_index)?;
+    }
+    Ok(())
+}
+
+fn tun_ioctl(file: &File, request: u32, arg: libc::c_ulong) -> anyhow::Result<()> {
+    let ret = unsafe { libc::ioctl(file.as_raw_fd(), request as _, arg) };
+    if ret < 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    Ok(())
+}
+
+// tuntap_deserialize decodes the IFLA_INFO_DATA the kernel reports for tun devices.
+// The fds are only known to the process which created the device.
+pub(crate) fn tuntap_deserialize(nlas: &[DefaultNla]) -> Tuntap {
+    let mut tuntap = Tuntap {
+        // the kernel omits IFLA_TUN_PERSIST only on very old versions
+        non_persist: true,
+        ..Default::default()
+    };
+    for nla in nlas {
+        match nla.kind() {
+            IFLA_TUN_OWNER => tuntap.owner = utils::default_nla_u32(nla),
+            IFLA_TUN_GROUP => tuntap.group = utils::default_nla_u32(nla),
+            IFLA_TUN_TYPE => tuntap.mode = utils::default_nla_u8(nla).map(u16::from).unwrap_or_default(),
+            IFLA_TUN_PI if utils::default_nla_u8(nla) == Some(0) => tuntap.flags |= TUNTAP_NO_PI,
+            IFLA_TUN_VNET_HDR if utils::default_nla_u8(nla).unwrap_or_default() != 0 => tuntap.flags |= TUNTAP_VNET_HDR,
+            IFLA_TUN_PERSIST => tuntap.non_persist = utils::default_nla_u8(nla).unwrap_or_default() == 0,
+            IFLA_TUN_MULTI_QUEUE if utils::default_nla_u8(nla).unwrap_or_default() != 0 => tuntap.flags |= TUNTAP_MULTI_QUEUE,
+            IFLA_TUN_NUM_QUEUES => tuntap.queues = utils::default_nla_u32(nla).unwrap_or_default() as i32,
+            IFLA_TUN_NUM_DISABLED_QUEUES => tuntap.disabled_queues = utils::default_nla_u32(nla).unwrap_or_default() as i32,
+            _ => {}
+        }
+    }
+    tuntap
+}
@@ -2,6 +2,8 @@ use std::net::IpAddr;
 use anyhow::{anyhow, bail};
 
 use netlink_packet_route::{AF_INET, AF_INET6};
+use netlink_packet_utils::nla::{DefaultNla, Nla, NlaBuffer, NlasIterator};
+use netlink_packet_utils::{Emitable, Parseable};
 
 use crate::nl_type::*;
 
@@ -19,6 +21,16 @@ pub fn ip_to_family(ip: &IpAddr) -> u8 {
     }
 }
 
+// bytes_to_ip_by_len decodes an address whose family is only known from its length,
+// e.g. NDA_DST of an AF_BRIDGE fdb entry
+pub fn bytes_to_ip_by_len(bytes: &[u8]) -> anyhow::Result<IpAddr> {
+    match bytes.len() {
+        4 => bytes_to_ip(bytes, FAMILY_V4),
+        16 => bytes_to_ip(bytes, FAMILY_V6),
+        n => Err(anyhow!("invalid ip bytes len: {}", n)),
+    }
+}
+
 pub fn bytes_to_ip(bytes: &[u8], family: Family) -> anyhow::Result<IpAddr> {
     match family {
         FAMILY_V4 => {
@@ -40,3 +52,42 @@ pub fn bytes_to_ip(bytes: &[u8], family: Family) -> anyhow::Result<IpAddr> {
         _ => Err(anyhow!("invalid family: {}", family)),
     }
 }
+
+pub fn default_nla_value(nla: &DefaultNla) -> Vec<u8> {
+    let mut value = vec![0u8; nla.value_len()];
+    nla.emit_value(&mut value);
+    value
+}
+
+// default_nla_u8/u16/u32 read a native endian value, None if the length does not match,
+// big endian attributes are converted by the caller with from_be
+pub fn default_nla_u8(nla: &DefaultNla) -> Option<u8> {
+    match default_nla_value(nla).as_slice() {
+        [v] => Some(*v),
+        _ => None,
+    }
+}
+
+pub fn default_nla_u16(nla: &DefaultNla) -> Option<u16> {
+    default_nla_value(nla).try_into().ok().map(u16::from_ne_bytes)
+}
+
+pub fn default_nla_u32(nla: &DefaultNla) -> Option<u32> {
+    default_nla_value(nla).try_into().ok().map(u32::from_ne_bytes)
+}
+
+// emit_nlas encodes nlas as the raw payload of an attribute the library does not know, e.g.
+// the IFLA_INFO_DATA of link kinds it has no typed data for
+pub fn emit_nlas<T: Nla>(nlas: &[T]) -> Vec<u8> {
+    let mut bytes = vec![0u8; nlas.buffer_len()];
+    nlas.emit(&mut bytes);
+    bytes
+}
+
+// parse_nlas decodes a raw attribute payload, skipping nlas that fail to parse
+pub fn parse_nlas<T>(bytes: &[u8]) -> Vec<T>
+    where T: for<'a> Parseable<NlaBuffer<&'a [u8]>> {
+    NlasIterator::new(bytes)
+        .filter_map(|nla| nla.ok().and_then(|nla| T::parse(&nla).ok()))
+        .collect()
+}
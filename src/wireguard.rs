@@ -0,0 +1,672 @@
+use std::net::{IpAddr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Context};
+use ipnetwork::IpNetwork;
+use netlink_packet_core::{NLM_F_ACK, NLM_F_DUMP};
+use netlink_packet_utils::nla::{self, DefaultNla, NlaBuffer, NlasIterator};
+use netlink_packet_utils::parsers::{parse_string, parse_u16, parse_u32, parse_u64, parse_u8};
+use netlink_packet_utils::{DecodeError, Emitable, Parseable};
+
+use crate::genl::{genl_execute, genl_family_id, GenlMessage};
+use crate::utils;
+
+pub const WG_GENL_NAME: &str = "wireguard";
+pub const WG_GENL_VERSION: u8 = 1;
+pub const WG_KEY_LEN: usize = 32;
+
+pub const WG_CMD_GET_DEVICE: u8 = 0;
+pub const WG_CMD_SET_DEVICE: u8 = 1;
+
+pub const WGDEVICE_F_REPLACE_PEERS: u32 = 1 << 0;
+
+pub const WGDEVICE_A_IFINDEX: u16 = 1;
+pub const WGDEVICE_A_IFNAME: u16 = 2;
+pub const WGDEVICE_A_PRIVATE_KEY: u16 = 3;
+pub const WGDEVICE_A_PUBLIC_KEY: u16 = 4;
+pub const WGDEVICE_A_FLAGS: u16 = 5;
+pub const WGDEVICE_A_LISTEN_PORT: u16 = 6;
+pub const WGDEVICE_A_FWMARK: u16 = 7;
+pub const WGDEVICE_A_PEERS: u16 = 8;
+
+pub const WGPEER_F_REMOVE_ME: u32 = 1 << 0;
+pub const WGPEER_F_REPLACE_ALLOWEDIPS: u32 = 1 << 1;
+pub const WGPEER_F_UPDATE_ONLY: u32 = 1 << 2;
+
+pub const WGPEER_A_PUBLIC_KEY: u16 = 1;
+pub const WGPEER_A_PRESHARED_KEY: u16 = 2;
+pub const WGPEER_A_FLAGS: u16 = 3;
+pub const WGPEER_A_ENDPOINT: u16 = 4;
+pub const WGPEER_A_PERSISTENT_KEEPALIVE_INTERVAL: u16 = 5;
+pub const WGPEER_A_LAST_HANDSHAKE_TIME: u16 = 6;
+pub const WGPEER_A_RX_BYTES: u16 = 7;
+pub const WGPEER_A_TX_BYTES: u16 = 8;
+pub const WGPEER_A_ALLOWEDIPS: u16 = 9;
+pub const WGPEER_A_PROTOCOL_VERSION: u16 = 10;
+
+pub const WGALLOWEDIP_A_FAMILY: u16 = 1;
+pub const WGALLOWEDIP_A_IPADDR: u16 = 2;
+pub const WGALLOWEDIP_A_CIDR_MASK: u16 = 3;
+
+// sizes of struct sockaddr_in, sockaddr_in6 and __kernel_timespec
+const SOCKADDR_IN_LEN: usize = 16;
+const SOCKADDR_IN6_LEN: usize = 28;
+const TIMESPEC_LEN: usize = 16;
+
+pub type WgKey = [u8; WG_KEY_LEN];
+
+// elements of a nested array are nested attributes typed by their index
+struct ArrayItem<'a, T>(u16, &'a [T]);
+
+impl<T: nla::Nla> nla::Nla for ArrayItem<'_, T> {
+    fn value_len(&self) -> usize {
+        self.1.buffer_len()
+    }
+
+    fn kind(&self) -> u16 {
+        self.0
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        self.1.emit(buffer)
+    }
+
+    fn is_nested(&self) -> bool {
+        true
+    }
+}
+
+fn array_items<T>(items: &[Vec<T>]) -> Vec<ArrayItem<'_, T>> {
+    items.iter().enumerate().map(|(i, item)| ArrayItem(i as u16, item.as_slice())).collect()
+}
+
+fn parse_array<T>(payload: &[u8]) -> Result<Vec<Vec<T>>, DecodeError>
+    where T: for<'a> Parseable<NlaBuffer<&'a [u8]>> {
+    let mut items = vec![];
+    for item in NlasIterator::new(payload) {
+        let item = item?;
+        let mut nlas = vec![];
+        for nla in NlasIterator::new(item.value()) {
+            nlas.push(T::parse(&nla?)?);
+        }
+        items.push(nlas);
+    }
+    Ok(items)
+}
+
+fn parse_key(payload: &[u8]) -> Result<WgKey, DecodeError> {
+    payload.try_into().map_err(|_| DecodeError::from("invalid wireguard key"))
+}
+
+fn emit_sockaddr(addr: &SocketAddr, buffer: &mut [u8]) {
+    buffer.fill(0);
+    match addr {
+        SocketAddr::V4(addr) => {
+            buffer[..2].copy_from_slice(&(libc::AF_INET as u16).to_ne_bytes());
+            buffer[2..4].copy_from_slice(&addr.port().to_be_bytes());
+            buffer[4..8].copy_from_slice(&addr.ip().octets());
+        }
+        SocketAddr::V6(addr) => {
+            buffer[..2].copy_from_slice(&(libc::AF_INET6 as u16).to_ne_bytes());
+            buffer[2..4].copy_from_slice(&addr.port().to_be_bytes());
+            buffer[4..8].copy_from_slice(&addr.flowinfo().to_be_bytes());
+            buffer[8..24].copy_from_slice(&addr.ip().octets());
+            buffer[24..28].copy_from_slice(&addr.scope_id().to_ne_bytes());
+        }
+    }
+}
+
+fn parse_sockaddr(payload: &[u8]) -> Result<SocketAddr, DecodeError> {
+    if payload.len() < 4 {
+        return Err(DecodeError::from("invalid wireguard endpoint"));
+    }
+    let family = u16::from_ne_bytes([payload[0], payload[1]]);
+    let port = u16::from_be_bytes([payload[2], payload[3]]);
+    match (family as i32, payload.len()) {
+        (libc::AF_INET, SOCKADDR_IN_LEN..) => {
+            let ip: [u8; 4] = payload[4..8].try_into().unwrap();
+            Ok(SocketAddr::V4(SocketAddrV4::new(ip.into(), port)))
+        }
+        (libc::AF_INET6, SOCKADDR_IN6_LEN..) => {
+            let flowinfo = u32::from_be_bytes(payload[4..8].try_into().unwrap());
+            let ip: [u8; 16] = payload[8..24].try_into().unwrap();
+            let scope_id = parse_u32(&payload[24..28])?;
+            Ok(SocketAddr::V6(SocketAddrV6::new(ip.into(), port, flowinfo, scope_id)))
+        }
+        _ => Err(DecodeError::from(format!("invalid wireguard endpoint family: {}", family))),
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum WgAllowedIpNla {
+    Family(u16),
+    IpAddr(IpAddr),
+    CidrMask(u8),
+    Other(DefaultNla),
+}
+
+impl nla::Nla for WgAllowedIpNla {
+    fn value_len(&self) -> usize {
+        match self {
+            WgAllowedIpNla::Family(_) => 2,
+            WgAllowedIpNla::IpAddr(ip) => utils::ip_to_bytes(ip).len(),
+            WgAllowedIpNla::CidrMask(_) => 1,
+            WgAllowedIpNla::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            WgAllowedIpNla::Family(_) => WGALLOWEDIP_A_FAMILY,
+            WgAllowedIpNla::IpAddr(_) => WGALLOWEDIP_A_IPADDR,
+            WgAllowedIpNla::CidrMask(_) => WGALLOWEDIP_A_CIDR_MASK,
+            WgAllowedIpNla::Other(attr) => attr.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            WgAllowedIpNla::Family(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            WgAllowedIpNla::IpAddr(ip) => buffer.copy_from_slice(&utils::ip_to_bytes(ip)),
+            WgAllowedIpNla::CidrMask(v) => buffer[0] = *v,
+            WgAllowedIpNla::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for WgAllowedIpNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            WGALLOWEDIP_A_FAMILY => WgAllowedIpNla::Family(parse_u16(payload).context("invalid WGALLOWEDIP_A_FAMILY value")?),
+            WGALLOWEDIP_A_IPADDR => WgAllowedIpNla::IpAddr(
+                utils::bytes_to_ip_by_len(payload).map_err(|e| DecodeError::from(e.to_string()))?,
+            ),
+            WGALLOWEDIP_A_CIDR_MASK => WgAllowedIpNla::CidrMask(parse_u8(payload).context("invalid WGALLOWEDIP_A_CIDR_MASK value")?),
+            _ => WgAllowedIpNla::Other(DefaultNla::parse(buf).context("invalid WGALLOWEDIP nla")?),
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum WgPeerNla {
+    PublicKey(WgKey),
+    PresharedKey(WgKey),
+    Flags(u32),
+    Endpoint(SocketAddr),
+    PersistentKeepalive(u16),
+    // time since the unix epoch
+    LastHandshakeTime(Duration),
+    RxBytes(u64),
+    TxBytes(u64),
+    AllowedIps(Vec<Vec<WgAllowedIpNla>>),
+    ProtocolVersion(u32),
+    Other(DefaultNla),
+}
+
+impl nla::Nla for WgPeerNla {
+    fn value_len(&self) -> usize {
+        match self {
+            WgPeerNla::PublicKey(_) | WgPeerNla::PresharedKey(_) => WG_KEY_LEN,
+            WgPeerNla::Flags(_) | WgPeerNla::ProtocolVersion(_) => 4,
+            WgPeerNla::Endpoint(SocketAddr::V4(_)) => SOCKADDR_IN_LEN,
+            WgPeerNla::Endpoint(SocketAddr::V6(_)) => SOCKADDR_IN6_LEN,
+            WgPeerNla::PersistentKeepalive(_) => 2,
+            WgPeerNla::LastHandshakeTime(_) => TIMESPEC_LEN,
+            WgPeerNla::RxBytes(_) | WgPeerNla::TxBytes(_) => 8,
+            WgPeerNla::AllowedIps(ips) => array_items(ips).as_slice().buffer_len(),
+            WgPeerNla::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            WgPeerNla::PublicKey(_) => WGPEER_A_PUBLIC_KEY,
+            WgPeerNla::PresharedKey(_) => WGPEER_A_PRESHARED_KEY,
+            WgPeerNla::Flags(_) => WGPEER_A_FLAGS,
+            WgPeerNla::Endpoint(_) => WGPEER_A_ENDPOINT,
+            WgPeerNla::PersistentKeepalive(_) => WGPEER_A_PERSISTENT_KEEPALIVE_INTERVAL,
+            WgPeerNla::LastHandshakeTime(_) => WGPEER_A_LAST_HANDSHAKE_TIME,
+            WgPeerNla::RxBytes(_) => WGPEER_A_RX_BYTES,
+            WgPeerNla::TxBytes(_) => WGPEER_A_TX_BYTES,
+            WgPeerNla::AllowedIps(_) => WGPEER_A_ALLOWEDIPS,
+            WgPeerNla::ProtocolVersion(_) => WGPEER_A_PROTOCOL_VERSION,
+            WgPeerNla::Other(attr) => attr.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            WgPeerNla::PublicKey(key) | WgPeerNla::PresharedKey(key) => buffer.copy_from_slice(key),
+            WgPeerNla::Flags(v) | WgPeerNla::ProtocolVersion(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            WgPeerNla::Endpoint(addr) => emit_sockaddr(addr, buffer),
+            WgPeerNla::PersistentKeepalive(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            WgPeerNla::LastHandshakeTime(time) => {
+                buffer[..8].copy_from_slice(&(time.as_secs() as i64).to_ne_bytes());
+                buffer[8..].copy_from_slice(&(time.subsec_nanos() as i64).to_ne_bytes());
+            }
+            WgPeerNla::RxBytes(v) | WgPeerNla::TxBytes(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            WgPeerNla::AllowedIps(ips) => array_items(ips).as_slice().emit(buffer),
+            WgPeerNla::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn is_nested(&self) -> bool {
+        matches!(self, WgPeerNla::AllowedIps(_))
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for WgPeerNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            WGPEER_A_PUBLIC_KEY => WgPeerNla::PublicKey(parse_key(payload)?),
+            WGPEER_A_PRESHARED_KEY => WgPeerNla::PresharedKey(parse_key(payload)?),
+            WGPEER_A_FLAGS => WgPeerNla::Flags(parse_u32(payload).context("invalid WGPEER_A_FLAGS value")?),
+            WGPEER_A_ENDPOINT => WgPeerNla::Endpoint(parse_sockaddr(payload)?),
+            WGPEER_A_PERSISTENT_KEEPALIVE_INTERVAL => WgPeerNla::PersistentKeepalive(
+                parse_u16(payload).context("invalid WGPEER_A_PERSISTENT_KEEPALIVE_INTERVAL value")?,
+            ),
+            WGPEER_A_LAST_HANDSHAKE_TIME => {
+                if payload.len() != TIMESPEC_LEN {
+                    return Err(DecodeError::from("invalid WGPEER_A_LAST_HANDSHAKE_TIME value"));
+                }
+                let secs = parse_u64(&payload[..8])?;
+                let nanos = parse_u64(&payload[8..])?;
+                WgPeerNla::LastHandshakeTime(Duration::new(secs, nanos as u32))
+            }
+            WGPEER_A_RX_BYTES => WgPeerNla::RxBytes(parse_u64(payload).context("invalid WGPEER_A_RX_BYTES value")?),
+            WGPEER_A_TX_BYTES => WgPeerNla::TxBytes(parse_u64(payload).context("invalid WGPEER_A_TX_BYTES value")?),
+            WGPEER_A_ALLOWEDIPS => WgPeerNla::AllowedIps(parse_array(payload)?),
+            WGPEER_A_PROTOCOL_VERSION => WgPeerNla::ProtocolVersion(parse_u32(payload).context("invalid WGPEER_A_PROTOCOL_VERSION value")?),
+            _ => WgPeerNla::Other(DefaultNla::parse(buf).context("invalid WGPEER nla")?),
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum WgDeviceNla {
+    IfIndex(u32),
+    IfName(String),
+    PrivateKey(WgKey),
+    PublicKey(WgKey),
+    Flags(u32),
+    ListenPort(u16),
+    Fwmark(u32),
+    Peers(Vec<Vec<WgPeerNla>>),
+    Other(DefaultNla),
+}
+
+impl nla::Nla for WgDeviceNla {
+    fn value_len(&self) -> usize {
+        match self {
+            WgDeviceNla::IfIndex(_) | WgDeviceNla::Flags(_) | WgDeviceNla::Fwmark(_) => 4,
+            WgDeviceNla::IfName(name) => name.len() + 1,
+            WgDeviceNla::PrivateKey(_) | WgDeviceNla::PublicKey(_) => WG_KEY_LEN,
+            WgDeviceNla::ListenPort(_) => 2,
+            WgDeviceNla::Peers(peers) => array_items(peers).as_slice().buffer_len(),
+            WgDeviceNla::Other(attr) => attr.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            WgDeviceNla::IfIndex(_) => WGDEVICE_A_IFINDEX,
+            WgDeviceNla::IfName(_) => WGDEVICE_A_IFNAME,
+            WgDeviceNla::PrivateKey(_) => WGDEVICE_A_PRIVATE_KEY,
+            WgDeviceNla::PublicKey(_) => WGDEVICE_A_PUBLIC_KEY,
+            WgDeviceNla::Flags(_) => WGDEVICE_A_FLAGS,
+            WgDeviceNla::ListenPort(_) => WGDEVICE_A_LISTEN_PORT,
+            WgDeviceNla::Fwmark(_) => WGDEVICE_A_FWMARK,
+            WgDeviceNla::Peers(_) => WGDEVICE_A_PEERS,
+            WgDeviceNla::Other(attr) => attr.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            WgDeviceNla::IfIndex(v) | WgDeviceNla::Flags(v) | WgDeviceNla::Fwmark(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            WgDeviceNla::IfName(name) => {
+                buffer[..name.len()].copy_from_slice(name.as_bytes());
+                buffer[name.len()] = 0;
+            }
+            WgDeviceNla::PrivateKey(key) | WgDeviceNla::PublicKey(key) => buffer.copy_from_slice(key),
+            WgDeviceNla::ListenPort(v) => buffer.copy_from_slice(&v.to_ne_bytes()),
+            WgDeviceNla::Peers(peers) => array_items(peers).as_slice().emit(buffer),
+            WgDeviceNla::Other(attr) => attr.emit_value(buffer),
+        }
+    }
+
+    fn is_nested(&self) -> bool {
+        matches!(self, WgDeviceNla::Peers(_))
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for WgDeviceNla {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            WGDEVICE_A_IFINDEX => WgDeviceNla::IfIndex(parse_u32(payload).context("invalid WGDEVICE_A_IFINDEX value")?),
+            WGDEVICE_A_IFNAME => WgDeviceNla::IfName(parse_string(payload).context("invalid WGDEVICE_A_IFNAME value")?),
+            WGDEVICE_A_PRIVATE_KEY => WgDeviceNla::PrivateKey(parse_key(payload)?),
+            WGDEVICE_A_PUBLIC_KEY => WgDeviceNla::PublicKey(parse_key(payload)?),
+            WGDEVICE_A_FLAGS => WgDeviceNla::Flags(parse_u32(payload).context("invalid WGDEVICE_A_FLAGS value")?),
+            WGDEVICE_A_LISTEN_PORT => WgDeviceNla::ListenPort(parse_u16(payload).context("invalid WGDEVICE_A_LISTEN_PORT value")?),
+            WGDEVICE_A_FWMARK => WgDeviceNla::Fwmark(parse_u32(payload).context("invalid WGDEVICE_A_FWMARK value")?),
+            WGDEVICE_A_PEERS => WgDeviceNla::Peers(parse_array(payload)?),
+            _ => WgDeviceNla::Other(DefaultNla::parse(buf).context("invalid WGDEVICE nla")?),
+        })
+    }
+}
+
+// WgDevice is the state of a wireguard link as reported by the kernel
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WgDevice {
+    pub link_index: u32,
+    pub name: String,
+    pub private_key: Option<WgKey>,
+    pub public_key: Option<WgKey>,
+    pub listen_port: u16,
+    pub fwmark: u32,
+    pub peers: Vec<WgPeer>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WgPeer {
+    pub public_key: WgKey,
+    pub preshared_key: Option<WgKey>,
+    pub endpoint: Option<SocketAddr>,
+    // seconds, 0 when disabled
+    pub persistent_keepalive: u16,
+    // None if there was no handshake yet
+    pub last_handshake_time: Option<SystemTime>,
+    pub rx_bytes: u64,
+    pub tx_bytes: u64,
+    pub allowed_ips: Vec<IpNetwork>,
+    pub protocol_version: u32,
+}
+
+impl WgPeer {
+    fn from_nlas(nlas: &[WgPeerNla]) -> anyhow::Result<Self> {
+        let public_key = nlas.iter()
+            .find_map(|nla| crate::unwrap_enum!(nla, WgPeerNla::PublicKey))
+            .ok_or_else(|| anyhow!("wireguard peer without public key"))?;
+        let mut peer = WgPeer {
+            public_key: *public_key,
+            preshared_key: None,
+            endpoint: None,
+            persistent_keepalive: 0,
+            last_handshake_time: None,
+            rx_bytes: 0,
+            tx_bytes: 0,
+            allowed_ips: vec![],
+            protocol_version: 0,
+        };
+        for nla in nlas {
+            match nla {
+                // the kernel reports an all zero key when there is no psk
+                WgPeerNla::PresharedKey(key) => peer.preshared_key = Some(*key).filter(|key| key.iter().any(|b| *b != 0)),
+                WgPeerNla::Endpoint(addr) => peer.endpoint = Some(*addr),
+                WgPeerNla::PersistentKeepalive(v) => peer.persistent_keepalive = *v,
+                WgPeerNla::LastHandshakeTime(time) => {
+                    peer.last_handshake_time = Some(UNIX_EPOCH + *time).filter(|_| !time.is_zero());
+                }
+                WgPeerNla::RxBytes(v) => peer.rx_bytes = *v,
+                WgPeerNla::TxBytes(v) => peer.tx_bytes = *v,
+                WgPeerNla::AllowedIps(ips) => peer.allowed_ips.extend(allowed_ips_from_nlas(ips)?),
+                WgPeerNla::ProtocolVersion(v) => peer.protocol_version = *v,
+                _ => {}
+            }
+        }
+        Ok(peer)
+    }
+}
+
+fn allowed_ips_from_nlas(ips: &[Vec<WgAllowedIpNla>]) -> anyhow::Result<Vec<IpNetwork>> {
+    let mut allowed_ips = vec![];
+    for nlas in ips {
+        let ip = nlas.iter().find_map(|nla| crate::unwrap_enum!(nla, WgAllowedIpNla::IpAddr));
+        let mask = nlas.iter().find_map(|nla| crate::unwrap_enum!(nla, WgAllowedIpNla::CidrMask));
+        if let (Some(ip), Some(mask)) = (ip, mask) {
+            allowed_ips.push(IpNetwork::new(*ip, *mask)?);
+        }
+    }
+    Ok(allowed_ips)
+}
+
+fn allowed_ips_nlas(allowed_ips: &[IpNetwork]) -> Vec<Vec<WgAllowedIpNla>> {
+    allowed_ips.iter()
+        .map(|net| vec![
+            WgAllowedIpNla::Family(utils::ip_to_family(&net.ip()) as u16),
+            WgAllowedIpNla::IpAddr(net.ip()),
+            WgAllowedIpNla::CidrMask(net.prefix()),
+        ])
+        .collect()
+}
+
+// WgConfig changes a wireguard link, unset fields are left untouched
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WgConfig {
+    pub private_key: Option<WgKey>,
+    pub listen_port: Option<u16>,
+    // 0 removes the fwmark
+    pub fwmark: Option<u32>,
+    // remove the peers which are not in `peers`
+    pub replace_peers: bool,
+    pub peers: Vec<WgPeerConfig>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WgPeerConfig {
+    pub public_key: WgKey,
+    pub remove: bool,
+    // only change the peer if it already exists
+    pub update_only: bool,
+    // an all zero key removes the psk
+    pub preshared_key: Option<WgKey>,
+    pub endpoint: Option<SocketAddr>,
+    // seconds, 0 disables the keepalive
+    pub persistent_keepalive: Option<u16>,
+    // replace the allowed ips instead of appending to them
+    pub replace_allowed_ips: bool,
+    pub allowed_ips: Vec<IpNetwork>,
+}
+
+impl WgPeerConfig {
+    fn nlas(&self) -> Vec<WgPeerNla> {
+        let mut nlas = vec![WgPeerNla::PublicKey(self.public_key)];
+        let mut flags = 0;
+        if self.remove {
+            flags |= WGPEER_F_REMOVE_ME;
+        }
+        if self.update_only {
+            flags |= WGPEER_F_UPDATE_ONLY;
+        }
+        if self.replace_allowed_ips {
+            flags |= WGPEER_F_REPLACE_ALLOWEDIPS;
+        }
+        if flags != 0 {
+            nlas.push(WgPeerNla::Flags(flags));
+        }
+        if self.remove {
+            return nlas;
+        }
+        if let Some(key) = self.preshared_key {
+            nlas.push(WgPeerNla::PresharedKey(key));
+        }
+        if let Some(endpoint) = self.endpoint {
+            nlas.push(WgPeerNla::Endpoint(endpoint));
+        }
+        if let Some(keepalive) = self.persistent_keepalive {
+            nlas.push(WgPeerNla::PersistentKeepalive(keepalive));
+        }
+        if !self.allowed_ips.is_empty() {
+            nlas.push(WgPeerNla::AllowedIps(allowed_ips_nlas(&self.allowed_ips)));
+        }
+        nlas
+    }
+}
+
+fn wg_msg(family_id: u16, cmd: u8, nlas: Vec<WgDeviceNla>) -> GenlMessage<WgDeviceNla> {
+    GenlMessage {
+        family_id,
+        cmd,
+        version: WG_GENL_VERSION,
+        nlas,
+    }
+}
+
+// wireguard_get returns the configuration and the peers of a wireguard link.
+// Equivalent to: `wg show $link`
+pub fn wireguard_get(link_index: u32) -> anyhow::Result<WgDevice> {
+    let family_id = genl_family_id(WG_GENL_NAME)?;
+    let msg = wg_msg(family_id, WG_CMD_GET_DEVICE, vec![WgDeviceNla::IfIndex(link_index)]);
+    let resp = genl_execute(msg, NLM_F_DUMP | NLM_F_ACK)?;
+
+    let mut device = WgDevice::default();
+    // large devices are split over several messages, a peer may continue in the next one
+    let mut last_key = None;
+    for msg in &resp {
+        for nla in &msg.nlas {
+            match nla {
+                WgDeviceNla::IfIndex(v) => device.link_index = *v,
+                WgDeviceNla::IfName(v) => device.name = v.clone(),
+                WgDeviceNla::PrivateKey(key) => device.private_key = Some(*key),
+                WgDeviceNla::PublicKey(key) => device.public_key = Some(*key),
+                WgDeviceNla::ListenPort(v) => device.listen_port = *v,
+                WgDeviceNla::Fwmark(v) => device.fwmark = *v,
+                WgDeviceNla::Peers(peers) => {
+                    for nlas in peers {
+                        let peer = WgPeer::from_nlas(nlas)?;
+                        match device.peers.last_mut() {
+                            Some(last) if last_key == Some(peer.public_key) => last.allowed_ips.extend(peer.allowed_ips),
+                            _ => {
+                                last_key = Some(peer.public_key);
+                                device.peers.push(peer);
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(device)
+}
+
+// wireguard_set applies `config` to a wireguard link.
+// Equivalent to: `wg set $link private-key $key listen-port $port peer $key ...`
+pub fn wireguard_set(link_index: u32, config: &WgConfig) -> anyhow::Result<()> {
+    let family_id = genl_family_id(WG_GENL_NAME)?;
+    let mut nlas = vec![WgDeviceNla::IfIndex(link_index)];
+    if let Some(key) = config.private_key {
+        nlas.push(WgDeviceNla::PrivateKey(key));
+    }
+    if let Some(port) = config.listen_port {
+        nlas.push(WgDeviceNla::ListenPort(port));
+    }
+    if let Some(fwmark) = config.fwmark {
+        nlas.push(WgDeviceNla::Fwmark(fwmark));
+    }
+    if config.replace_peers {
+        nlas.push(WgDeviceNla::Flags(WGDEVICE_F_REPLACE_PEERS));
+    }
+    if !config.peers.is_empty() {
+        nlas.push(WgDeviceNla::Peers(config.peers.iter().map(WgPeerConfig::nlas).collect()));
+    }
+    genl_execute(wg_msg(family_id, WG_CMD_SET_DEVICE, nlas), NLM_F_ACK)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use netlink_packet_core::{NetlinkDeserializable, NetlinkHeader, NetlinkSerializable};
+
+    use crate::nl_type::Wireguard;
+    use crate::{link_add, link_by_name, link_del, Link, LinkAttrs, LinkKind};
+
+    use super::*;
+
+    #[test]
+    fn test_wireguard_parse() -> anyhow::Result<()> {
+        let peer = WgPeerConfig {
+            public_key: [1; WG_KEY_LEN],
+            preshared_key: Some([2; WG_KEY_LEN]),
+            endpoint: Some("[fd00::1]:51820".parse()?),
+            persistent_keepalive: Some(25),
+            replace_allowed_ips: true,
+            allowed_ips: vec!["10.0.0.0/24".parse()?, "fd00::/64".parse()?],
+            ..Default::default()
+        };
+        let mut peer_nlas = peer.nlas();
+        peer_nlas.push(WgPeerNla::LastHandshakeTime(Duration::from_secs(1700000000)));
+        peer_nlas.push(WgPeerNla::RxBytes(100));
+        let msg = wg_msg(30, WG_CMD_GET_DEVICE, vec![
+            WgDeviceNla::IfName("wg0".to_string()),
+            WgDeviceNla::ListenPort(51820),
+            WgDeviceNla::Peers(vec![peer_nlas]),
+        ]);
+        let mut buf = vec![0u8; msg.buffer_len()];
+        msg.serialize(&mut buf);
+        let mut header = NetlinkHeader::default();
+        header.message_type = 30;
+        let parsed = GenlMessage::<WgDeviceNla>::deserialize(&header, &buf)?;
+        assert_eq!(parsed, msg);
+
+        let WgDeviceNla::Peers(peers) = &parsed.nlas[2] else { unreachable!() };
+        let found = WgPeer::from_nlas(&peers[0])?;
+        assert_eq!(found.public_key, peer.public_key);
+        assert_eq!(found.preshared_key, peer.preshared_key);
+        assert_eq!(found.endpoint, peer.endpoint);
+        assert_eq!(found.persistent_keepalive, 25);
+        assert_eq!(found.allowed_ips, peer.allowed_ips);
+        assert_eq!(found.last_handshake_time, Some(UNIX_EPOCH + Duration::from_secs(1700000000)));
+        assert_eq!(found.rx_bytes, 100);
+        Ok(())
+    }
+
+    #[test]
+    fn test_wireguard_set_get() -> anyhow::Result<()> {
+        let link = Link {
+            link_attrs: LinkAttrs {
+                name: "wg234".to_string(),
+                ..Default::default()
+            },
+            link_kind: LinkKind::Wireguard(Wireguard::default()),
+        };
+        link_add(&link)?;
+        let link_index = link_by_name("wg234")?.unwrap().link_attrs.index;
+
+        let result = (|| {
+            let peer = WgPeerConfig {
+                public_key: [7; WG_KEY_LEN],
+                endpoint: Some("10.0.0.2:51820".parse()?),
+                persistent_keepalive: Some(25),
+                allowed_ips: vec!["10.234.0.0/16".parse()?],
+                ..Default::default()
+            };
+            wireguard_set(link_index, &WgConfig {
+                private_key: Some([0x48; WG_KEY_LEN]),
+                listen_port: Some(51820),
+                fwmark: Some(0x234),
+                replace_peers: true,
+                peers: vec![peer],
+            })?;
+            wireguard_get(link_index)
+        })();
+        link_del(link_index)?;
+
+        let device = result?;
+        assert_eq!(device.name, "wg234");
+        assert_eq!((device.listen_port, device.fwmark), (51820, 0x234));
+        assert!(device.public_key.is_some());
+        assert_eq!(device.peers.len(), 1);
+        let peer = &device.peers[0];
+        assert_eq!(peer.public_key, [7; WG_KEY_LEN]);
+        assert_eq!(peer.endpoint, Some("10.0.0.2:51820".parse()?));
+        assert_eq!(peer.persistent_keepalive, 25);
+        assert_eq!(peer.allowed_ips, vec!["10.234.0.0/16".parse::<IpNetwork>()?]);
+        assert_eq!(peer.last_handshake_time, None);
+        Ok(())
+    }
+}